@@ -0,0 +1,1272 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use thiserror::Error;
+
+use crate::diagnostic::Part;
+use crate::expression::{is_identifier, unescape, Expr};
+use crate::{
+    AssembleErrors, DecodedInstruction, EncodeError, Instruction, Locale, Localize, Mov,
+    OperandFormat, Operands, ParseInstructionError, ParseRegisterError, Register, SourceLine, Span,
+    StackOperand, Word, MEMORY_SIZE,
+};
+
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub enum AssembleErrorKind {
+    UnknownMnemonic(#[from] ParseInstructionError),
+    InvalidRegister(#[from] ParseRegisterError),
+    OperandCount {
+        instruction: Instruction,
+        expected: usize,
+        found: usize,
+    },
+    InvalidOperand {
+        instruction: Instruction,
+        operand: String,
+    },
+    InvalidNumber {
+        text: String,
+    },
+    ValueOutOfRange {
+        value: i64,
+    },
+    InvalidExpression {
+        text: String,
+    },
+    ExpressionTooDeep {
+        text: String,
+    },
+    DivisionByZero,
+    NotConstant {
+        text: String,
+    },
+    CyclicConstant {
+        name: String,
+    },
+    InvalidCharacter {
+        text: String,
+    },
+    InvalidString {
+        text: String,
+    },
+    InvalidDirective {
+        directive: String,
+        operands: String,
+    },
+    AddressOutOfRange {
+        address: usize,
+    },
+    InvalidLabel {
+        label: String,
+    },
+    DuplicateLabel {
+        label: String,
+    },
+    UndefinedLabel {
+        label: String,
+    },
+    Encode(#[from] EncodeError),
+    Include {
+        path: String,
+        message: String,
+    },
+    RecursiveInclude {
+        path: String,
+    },
+    DuplicateMacro {
+        name: String,
+    },
+    MacroArgumentCount {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+    RecursiveMacro {
+        name: String,
+    },
+    MacroExpansionTooLarge {
+        name: String,
+        lines: usize,
+    },
+    UnexpectedDirective {
+        directive: String,
+    },
+    UnterminatedBlock {
+        directive: String,
+    },
+    ProgramTooLarge {
+        size: usize,
+    },
+}
+
+/// Erro de montagem, no trecho `span` do código-fonte.
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub struct AssembleError {
+    pub kind: AssembleErrorKind,
+    pub span: Span,
+}
+
+/// Erro de montagem nos *bytes* `range` de uma linha, antes de ser associado à posição da linha.
+struct LineError {
+    kind: AssembleErrorKind,
+    range: Range<usize>,
+}
+
+/// Associa um erro à parte `part` da linha.
+fn at<E: Into<AssembleErrorKind>>(part: Part) -> impl FnOnce(E) -> LineError {
+    let range = part.range();
+    move |kind| LineError {
+        kind: kind.into(),
+        range,
+    }
+}
+
+/// Operando de uma instrução no código-fonte.
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    /// `R0` a `R7`, `SP` ou `FR`.
+    Register(Register),
+    /// `END` ou `N`, sem `#`.
+    Address(Expr),
+    /// `#NR`.
+    Immediate(Expr),
+}
+
+/// Conteúdo de uma linha lido na primeira passagem, com os rótulos ainda não resolvidos. Cada
+/// operando é guardado junto do seu texto, para indicar a posição dos erros.
+#[derive(Debug)]
+enum StatementKind<'a> {
+    Instruction {
+        instruction: Instruction,
+        operands: Vec<(Operand, Part<'a>)>,
+    },
+    /// Palavras emitidas diretamente, por `var` e `string`.
+    Words(Vec<Word>),
+    /// `static target + #offset, #value`: inicializa uma posição da memória sem emitir palavras.
+    Static {
+        target: (Expr, Part<'a>),
+        value: (Expr, Part<'a>),
+    },
+}
+
+#[derive(Debug)]
+struct Statement<'a> {
+    /// Índice da linha de onde a instrução foi lida.
+    source: usize,
+    /// Texto da instrução, sem o rótulo e o comentário.
+    text: Part<'a>,
+    kind: StatementKind<'a>,
+}
+
+impl StatementKind<'_> {
+    /// Retorna a quantidade de palavras emitidas.
+    fn size(&self) -> usize {
+        match self {
+            StatementKind::Instruction { instruction, .. } => instruction.size(),
+            StatementKind::Words(words) => words.len(),
+            StatementKind::Static { .. } => 0,
+        }
+    }
+}
+
+/// Retorna a posição da primeira ocorrência de `pattern` em `text` fora de literais de
+/// caractere (`'A'`) e de *strings* (`"..."`).
+pub(crate) fn find_unquoted(text: &str, pattern: char) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == pattern => return Some(i),
+            None => {}
+        }
+    }
+
+    None
+}
+
+/// Separa `text` em cada `pattern` fora de literais de caractere e de *strings*.
+pub(crate) fn split_unquoted(text: Part, pattern: char) -> Vec<Part> {
+    let mut parts = vec![];
+    let mut rest = text;
+
+    while let Some(i) = find_unquoted(&rest, pattern) {
+        let (part, after) = rest.split_around(i);
+        parts.push(part);
+        rest = after;
+    }
+
+    parts.push(rest);
+    parts
+}
+
+/// Retorna o conteúdo, ainda com os escapes, do literal de *string* `text`. O literal termina
+/// nas primeiras aspas sem escape, que devem ser o último caractere de `text`.
+pub(crate) fn string_literal(text: Part) -> Option<Part> {
+    let inner = text.strip_prefix('"')?;
+    let mut escaped = false;
+
+    for (i, c) in inner.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return (i + 1 == inner.len()).then(|| inner.slice(0..i)),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Converte o valor `value` de uma expressão em uma palavra. Valores negativos são escritos em
+/// complemento de dois.
+fn to_word(value: i64) -> Result<Word, AssembleErrorKind> {
+    match value {
+        0..=0xffff => Ok(Word(value as u16)),
+        -0x8000..=-1 => Ok(Word::from_signed(value as i16)),
+        _ => Err(AssembleErrorKind::ValueOutOfRange { value }),
+    }
+}
+
+/// Indica se `text` tem a forma de um registrador, como `R3` ou `r12`, mesmo que não exista.
+fn is_register_like(text: &str) -> bool {
+    let mut chars = text.chars();
+
+    matches!(chars.next(), Some('R' | 'r'))
+        && !chars.as_str().is_empty()
+        && chars.all(|c| c.is_ascii_digit())
+}
+
+/// Indica se `name` pode ser usado como rótulo ou constante.
+fn is_symbol(name: &str) -> bool {
+    is_identifier(name) && name.parse::<Register>().is_err() && !is_register_like(name)
+}
+
+fn parse_operand(text: &str) -> Result<Operand, AssembleErrorKind> {
+    match text.parse() {
+        Ok(register) => return Ok(Operand::Register(register)),
+        Err(e) if is_register_like(text) => return Err(e.into()),
+        Err(_) => {}
+    }
+
+    match text.strip_prefix('#') {
+        Some(value) => Expr::parse(value).map(Operand::Immediate),
+        None => Expr::parse(text).map(Operand::Address),
+    }
+}
+
+/// Lê o mnemônico e os operandos de uma instrução, retornando os erros de todos eles.
+fn read_instruction<'a>(
+    mnemonic: Part<'a>,
+    operands: Part<'a>,
+) -> Result<StatementKind<'a>, Vec<LineError>> {
+    let mut errors = vec![];
+
+    let instruction = match mnemonic.parse::<Instruction>() {
+        Ok(instruction) => Some(instruction),
+        Err(e) => {
+            errors.push(at(mnemonic)(e));
+            None
+        }
+    };
+
+    let mut parsed = vec![];
+
+    if !operands.is_empty() {
+        for operand in split_unquoted(operands, ',') {
+            let operand = operand.trim();
+
+            match parse_operand(&operand) {
+                Ok(value) => parsed.push((value, operand)),
+                Err(e) => errors.push(at(operand)(e)),
+            }
+        }
+    }
+
+    match instruction {
+        Some(instruction) if errors.is_empty() => Ok(StatementKind::Instruction {
+            instruction,
+            operands: parsed,
+        }),
+        _ => Err(errors),
+    }
+}
+
+/// Retorna a expressão do operando `text`, sem o `#` dos valores imediatos.
+fn expression(text: Part) -> Part {
+    text.strip_prefix('#').unwrap_or(text).trim_start()
+}
+
+/// Remove o comentário (`; ...`) e os espaços das pontas de `line`.
+pub(crate) fn strip_comment(line: Part) -> Part {
+    match find_unquoted(&line, ';') {
+        Some(i) => line.slice(0..i).trim(),
+        None => line.trim(),
+    }
+}
+
+/// Separa a primeira palavra de `text` do restante.
+pub(crate) fn split_word(text: Part) -> (Part, Part) {
+    match text.find(char::is_whitespace) {
+        Some(i) => {
+            let (word, rest) = text.split_around(i);
+            (word, rest.trim())
+        }
+        None => (text, text.slice(text.len()..text.len())),
+    }
+}
+
+/// Indica se `word` é a diretiva `EQU`.
+fn is_equ(word: &str) -> bool {
+    word.eq_ignore_ascii_case("equ")
+}
+
+/// Retorna o nome e o valor da constante definida por `NOME EQU valor`, `NOME: EQU valor` ou
+/// `define NOME valor`, sendo `label` e `rest` as partes da linha separadas por [`split_label`].
+pub(crate) fn constant_definition<'a>(
+    label: Option<Part<'a>>,
+    rest: Part<'a>,
+) -> Option<(Part<'a>, Part<'a>)> {
+    let (mnemonic, operands) = split_word(rest);
+    let (first, second) = split_word(operands);
+
+    match label {
+        Some(label) if is_equ(&mnemonic) => Some((label, operands)),
+        None if is_equ(&first) => Some((mnemonic, second)),
+        None if mnemonic.eq_ignore_ascii_case("define") => Some((first, second)),
+        _ => None,
+    }
+}
+
+/// Separa o rótulo (`rotulo:`) do restante da linha, já sem o comentário. O rótulo não é
+/// validado.
+pub(crate) fn split_label(line: Part) -> (Option<Part>, Part) {
+    match find_unquoted(&line, ':') {
+        Some(i) => {
+            let (label, rest) = line.split_around(i);
+            (Some(label.trim()), rest.trim())
+        }
+        None => (None, line),
+    }
+}
+
+/// Um nome definido no código-fonte.
+#[derive(Debug)]
+pub(crate) enum Symbol {
+    /// Um rótulo, com o endereço em que foi definido.
+    Label(Word),
+    /// Uma constante, definida com `EQU` ou `define`.
+    Constant(Expr),
+}
+
+/// Tabela dos rótulos e das constantes do código-fonte.
+#[derive(Debug, Default)]
+pub(crate) struct Symbols(HashMap<String, Symbol>);
+
+impl Symbols {
+    /// Define o símbolo `name`. Se ele já existir, a primeira definição é mantida.
+    pub(crate) fn insert(&mut self, name: &str, symbol: Symbol) -> Result<(), AssembleErrorKind> {
+        match self.0.entry(name.to_string()) {
+            Entry::Occupied(_) => Err(AssembleErrorKind::DuplicateLabel {
+                label: name.to_string(),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(symbol);
+                Ok(())
+            }
+        }
+    }
+
+    /// Retorna o valor do símbolo `name`, buscando as constantes em `values`. Uma constante que
+    /// ainda não foi avaliada é guardada em `missing`.
+    fn value(
+        &self,
+        name: &str,
+        labels: bool,
+        values: &HashMap<String, i64>,
+        missing: &mut Option<String>,
+    ) -> Result<i64, AssembleErrorKind> {
+        match self.0.get(name) {
+            Some(Symbol::Label(address)) if labels => Ok(i64::from(address.0)),
+            Some(Symbol::Label(_)) => Err(AssembleErrorKind::NotConstant {
+                text: name.to_string(),
+            }),
+            Some(Symbol::Constant(_)) => match values.get(name) {
+                Some(&value) => Ok(value),
+                None => {
+                    *missing = Some(name.to_string());
+                    // O erro é descartado: a constante é avaliada e a expressão, reavaliada.
+                    Err(AssembleErrorKind::CyclicConstant {
+                        name: name.to_string(),
+                    })
+                }
+            },
+            None => Err(AssembleErrorKind::UndefinedLabel {
+                label: name.to_string(),
+            }),
+        }
+    }
+
+    /// Avalia a expressão `expr`. Rótulos só são aceitos se `labels` for verdadeiro.
+    ///
+    /// As constantes são avaliadas com uma pilha, e não com recursão, para que uma longa cadeia
+    /// de constantes definidas umas pelas outras não esgote a pilha de execução. Cada constante
+    /// é avaliada uma única vez.
+    pub(crate) fn evaluate(&self, expr: &Expr, labels: bool) -> Result<i64, AssembleErrorKind> {
+        let mut values = HashMap::new();
+        // Constantes sendo avaliadas, da primeira usada por `expr` à mais recente.
+        let mut stack: Vec<&str> = vec![];
+        let mut visiting: HashSet<&str> = HashSet::new();
+
+        loop {
+            let current = match stack.last() {
+                Some(&name) => match &self.0[name] {
+                    Symbol::Constant(expr) => expr,
+                    Symbol::Label(_) => unreachable!("apenas constantes são empilhadas"),
+                },
+                None => expr,
+            };
+
+            let mut missing = None;
+            let value =
+                current.evaluate(&mut |name| self.value(name, labels, &values, &mut missing));
+
+            let Some(name) = missing else {
+                match stack.pop() {
+                    Some(name) => {
+                        visiting.remove(name);
+                        values.insert(name.to_string(), value?);
+                    }
+                    None => return value,
+                }
+
+                continue;
+            };
+
+            let (name, _) = self.0.get_key_value(&name).unwrap();
+
+            if !visiting.insert(name) {
+                return Err(AssembleErrorKind::CyclicConstant { name: name.clone() });
+            }
+
+            stack.push(name);
+        }
+    }
+}
+
+/// Montador de duas passagens para o *assembly* do Processador ICMC.
+///
+/// A primeira passagem lê cada linha, no formato `rotulo: MNEMONICO op1, op2 ; comentário`, e
+/// calcula o endereço de cada rótulo a partir do tamanho das instruções. A segunda passagem
+/// resolve os rótulos e codifica as instruções.
+struct Assembler<'a> {
+    symbols: Symbols,
+    statements: Vec<Statement<'a>>,
+    size: usize,
+}
+
+impl<'a> Assembler<'a> {
+    fn new() -> Self {
+        Self {
+            symbols: Symbols::default(),
+            statements: vec![],
+            size: 0,
+        }
+    }
+
+    /// Primeira passagem: lê a linha de índice `source` do código-fonte, retornando os erros
+    /// encontrados no rótulo, no mnemônico e em cada operando. Uma instrução com um rótulo
+    /// inválido ainda é lida, para que os seus erros também sejam encontrados.
+    fn read_line(&mut self, source: usize, line: &'a str) -> Vec<LineError> {
+        let (label, rest) = split_label(strip_comment(Part::line(line)));
+        let mut errors = vec![];
+
+        let invalid = |label: Part| {
+            at(label)(AssembleErrorKind::InvalidLabel {
+                label: label.to_string(),
+            })
+        };
+
+        // `NOME: EQU valor` define uma constante, e não um rótulo.
+        if let Some((name, value)) = constant_definition(label, rest) {
+            if !is_symbol(&name) {
+                errors.push(invalid(name));
+            }
+
+            match Expr::parse(&value) {
+                Ok(expr) if errors.is_empty() => {
+                    let result = self.symbols.insert(&name, Symbol::Constant(expr));
+                    errors.extend(result.map_err(at(name)).err());
+                }
+                Ok(_) => {}
+                Err(e) => errors.push(at(value)(e)),
+            }
+
+            return errors;
+        }
+
+        if let Some(label) = label {
+            let result = match is_symbol(&label) {
+                true => self
+                    .symbols
+                    .insert(&label, Symbol::Label(Word(self.size as u16)))
+                    .map_err(at(label)),
+                false => Err(invalid(label)),
+            };
+
+            errors.extend(result.err());
+        }
+
+        if rest.is_empty() {
+            return errors;
+        }
+
+        let (mnemonic, operands) = split_word(rest);
+
+        let kind = match mnemonic.to_ascii_lowercase().as_str() {
+            "var" | "string" | "static" => {
+                self.read_directive(mnemonic, operands).map_err(|e| vec![e])
+            }
+            _ => read_instruction(mnemonic, operands),
+        };
+
+        match kind {
+            Ok(kind) => {
+                self.size += kind.size();
+                self.statements.push(Statement {
+                    source,
+                    text: rest,
+                    kind,
+                });
+            }
+            Err(e) => errors.extend(e),
+        }
+
+        errors
+    }
+
+    /// Avalia a expressão `expr`. Na primeira passagem, `labels` deve ser falso, já que os
+    /// endereços dos rótulos ainda não são conhecidos.
+    fn evaluate(&self, expr: &Expr, labels: bool) -> Result<Word, AssembleErrorKind> {
+        to_word(self.symbols.evaluate(expr, labels)?)
+    }
+
+    fn resolve(&self, expr: &Expr) -> Result<Word, AssembleErrorKind> {
+        self.evaluate(expr, true)
+    }
+
+    /// Lê os operandos das diretivas `var #N`, `string "..."` e `static rotulo + #offset, #valor`.
+    fn read_directive(
+        &self,
+        directive: Part<'a>,
+        operands: Part<'a>,
+    ) -> Result<StatementKind<'a>, LineError> {
+        let invalid = || {
+            let part = if operands.is_empty() {
+                directive
+            } else {
+                operands
+            };
+
+            at(part)(AssembleErrorKind::InvalidDirective {
+                directive: directive.to_string(),
+                operands: operands.to_string(),
+            })
+        };
+
+        match directive.to_ascii_lowercase().as_str() {
+            "var" => {
+                let n = operands.strip_prefix('#').ok_or_else(invalid)?;
+                let expr = Expr::parse(&n).map_err(at(n))?;
+                let count = self.symbols.evaluate(&expr, false).map_err(at(n))?;
+
+                if count <= 0 {
+                    return Err(at(n)(AssembleErrorKind::ValueOutOfRange { value: count }));
+                }
+
+                let size =
+                    usize::try_from(count).map_or(usize::MAX, |c| self.size.saturating_add(c));
+                if size > MEMORY_SIZE {
+                    return Err(at(n)(AssembleErrorKind::ProgramTooLarge { size }));
+                }
+
+                Ok(StatementKind::Words(vec![Word::ZERO; size - self.size]))
+            }
+            "string" => {
+                let text = string_literal(operands).ok_or_else(invalid)?;
+
+                let mut words: Vec<Word> = unescape(&text)
+                    .ok_or_else(|| {
+                        at(operands)(AssembleErrorKind::InvalidString {
+                            text: operands.to_string(),
+                        })
+                    })?
+                    .into_iter()
+                    .map(Word)
+                    .collect();
+                words.push(Word::ZERO);
+
+                Ok(StatementKind::Words(words))
+            }
+            _ => {
+                let [target, value] = split_unquoted(operands, ',')[..] else {
+                    return Err(invalid());
+                };
+                let (target, value) = (target.trim(), value.trim());
+
+                // Em `rotulo + #offset`, o `#` do deslocamento é opcional.
+                let target_expr: String = split_unquoted(target, '#')
+                    .iter()
+                    .map(Part::as_str)
+                    .collect();
+                let value_expr = value.strip_prefix('#').ok_or_else(invalid)?;
+
+                Ok(StatementKind::Static {
+                    target: (Expr::parse(&target_expr).map_err(at(target))?, target),
+                    value: (Expr::parse(&value_expr).map_err(at(value))?, value),
+                })
+            }
+        }
+    }
+
+    /// Segunda passagem: retorna o endereço e o valor de uma diretiva `static`.
+    fn initialize(
+        &self,
+        (target, target_text): &(Expr, Part<'a>),
+        (value, value_text): &(Expr, Part<'a>),
+    ) -> Result<(usize, Word), LineError> {
+        let address = usize::from(self.resolve(target).map_err(at(*target_text))?);
+        let value = self.resolve(value).map_err(at(*value_text))?;
+
+        if address >= MEMORY_SIZE {
+            let kind = AssembleErrorKind::AddressOutOfRange { address };
+            return Err(at(*target_text)(kind));
+        }
+
+        Ok((address, value))
+    }
+
+    /// Segunda passagem: resolve os rótulos e codifica a instrução `text`.
+    fn encode(
+        &self,
+        text: Part<'a>,
+        instruction: Instruction,
+        operands: &[(Operand, Part<'a>)],
+    ) -> Result<Vec<Word>, LineError> {
+        let format = instruction.format();
+
+        let expected = match format {
+            OperandFormat::None => 0,
+            OperandFormat::Rx | OperandFormat::Stack | OperandFormat::Address => 1,
+            OperandFormat::RxRyRz => 3,
+            _ => 2,
+        };
+
+        if operands.len() != expected {
+            return Err(at(text)(AssembleErrorKind::OperandCount {
+                instruction,
+                expected,
+                found: operands.len(),
+            }));
+        }
+
+        let invalid = |&(_, operand): &(Operand, Part<'a>)| {
+            at(operand)(AssembleErrorKind::InvalidOperand {
+                instruction,
+                operand: operand.to_string(),
+            })
+        };
+
+        let register = |operand: &(Operand, Part<'a>)| match operand {
+            (Operand::Register(register), _) if register.is_general_purpose() => Ok(*register),
+            operand => Err(invalid(operand)),
+        };
+
+        let address = |operand: &(Operand, Part<'a>)| match operand {
+            (Operand::Address(value), text) => self.resolve(value).map_err(at(expression(*text))),
+            operand => Err(invalid(operand)),
+        };
+
+        let immediate = |operand: &(Operand, Part<'a>)| match operand {
+            (Operand::Immediate(value), text) => self.resolve(value).map_err(at(expression(*text))),
+            operand => Err(invalid(operand)),
+        };
+
+        let mut decoded = DecodedInstruction::new(instruction, Operands::None);
+
+        match (format, operands) {
+            (OperandFormat::None, []) => {}
+            (OperandFormat::Rx, [rx]) => {
+                decoded.operands = Operands::Rx { rx: register(rx)? };
+            }
+            (OperandFormat::RxRy, [rx, ry]) => {
+                decoded.operands = Operands::RxRy {
+                    rx: register(rx)?,
+                    ry: register(ry)?,
+                };
+            }
+            (OperandFormat::RxRyRz, [rx, ry, rz]) => {
+                decoded.operands = Operands::RxRyRz {
+                    rx: register(rx)?,
+                    ry: register(ry)?,
+                    rz: register(rz)?,
+                };
+            }
+            (OperandFormat::Shift, [rx, n]) => {
+                let n = match n {
+                    (Operand::Address(value) | Operand::Immediate(value), text) => {
+                        self.resolve(value).map_err(at(expression(*text)))?
+                    }
+                    operand => return Err(invalid(operand)),
+                };
+
+                decoded.operands = Operands::Shift {
+                    rx: register(rx)?,
+                    n: usize::from(n),
+                };
+            }
+            (OperandFormat::Mov, [rx, ry]) => {
+                let mov = match (rx, ry) {
+                    ((Operand::Register(Register::SP), _), rx) => Mov::ToSp { rx: register(rx)? },
+                    (rx, (Operand::Register(Register::SP), _)) => Mov::FromSp { rx: register(rx)? },
+                    (rx, ry) => Mov::Register {
+                        rx: register(rx)?,
+                        ry: register(ry)?,
+                    },
+                };
+
+                decoded.operands = Operands::Mov(mov);
+            }
+            (OperandFormat::Stack, [operand]) => {
+                let operand = match operand {
+                    (Operand::Register(Register::FR), _) => StackOperand::FlagRegister,
+                    rx => StackOperand::Register(register(rx)?),
+                };
+
+                decoded.operands = Operands::Stack(operand);
+            }
+            (OperandFormat::RxAddress, [rx, end]) => {
+                decoded.operands = Operands::Rx { rx: register(rx)? };
+                decoded.address = Some(address(end)?);
+            }
+            (OperandFormat::RxImmediate, [rx, nr]) => {
+                decoded.operands = Operands::Rx { rx: register(rx)? };
+                decoded.immediate = Some(immediate(nr)?);
+            }
+            (OperandFormat::AddressRx, [end, rx]) => {
+                decoded.operands = Operands::Rx { rx: register(rx)? };
+                decoded.address = Some(address(end)?);
+            }
+            (OperandFormat::AddressImmediate, [end, nr]) => {
+                decoded.address = Some(address(end)?);
+                decoded.immediate = Some(immediate(nr)?);
+            }
+            (OperandFormat::Address, [end]) => {
+                decoded.address = Some(address(end)?);
+            }
+            _ => unreachable!("a quantidade de operandos já foi verificada"),
+        }
+
+        decoded.encode().map_err(at(text))
+    }
+}
+
+/// Monta o código-fonte `source`, retornando as palavras do programa a partir do endereço 0.
+///
+/// Cada linha tem o formato `rotulo: MNEMONICO op1, op2 ; comentário`, em que todas as partes
+/// são opcionais. Os operandos seguem os blocos `# Uso` da documentação de cada instrução:
+/// registradores (`R0` a `R7`, `SP` e `FR`), endereços (`END`) e valores imediatos (`#NR`),
+/// escritos como números decimais, hexadecimais (`0x`) ou binários (`0b`), como caracteres
+/// (`'A'`), como rótulos ou como expressões que os combinam com `+ - * / % << >> & | ^ ~` e
+/// parênteses, como `#'A' + 3072` ou `loop + 2`.
+///
+/// Além das instruções, são aceitas as diretivas:
+/// * `NOME EQU valor` e `define NOME valor`: definem uma constante, que pode ser usada em
+///   qualquer expressão, inclusive antes da sua definição;
+/// * `var #N`: reserva `N` palavras zeradas. `N` não pode depender de rótulos;
+/// * `string "..."`: emite os caracteres da *string*, seguidos de `\0`;
+/// * `static rotulo + #offset, #valor`: inicializa a palavra no endereço `rotulo + offset` com
+///   `valor`, sem emitir palavras.
+///
+/// Para usar `include`, macros e montagem condicional, veja o
+/// [`Preprocessor`](crate::Preprocessor).
+///
+/// Se houver erros, todos são retornados em [`AssembleErrors`], cada um com o trecho da linha
+/// em que foi encontrado.
+///
+/// ## Exemplo
+///
+/// ```
+/// use isa::*;
+///
+/// let program = assemble(
+///     "
+///     loop:
+///         INC R1          ; R1 ← R1 + 1
+///         CMP R1, R2
+///         JNE loop
+///         STORE 0x00ff, R1
+///         HALT
+///     ",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     vec![
+///         Word(0b100100_001_000_000_0),
+///         Word(0b010110_001_010_000_0),
+///         Word(0b000010_001_000_000_0),
+///         Word(0x0000),
+///         Word(0b110001_001_000_000_0),
+///         Word(0x00ff),
+///         Word(0b001111_000_000_000_0),
+///     ],
+///     program
+/// );
+/// ```
+pub fn assemble(source: &str) -> Result<Vec<Word>, AssembleErrors> {
+    let lines: Vec<&str> = source.lines().collect();
+
+    assemble_from(&lines, |i, range| Span::new(None, i + 1, lines[i], range))
+        .map_err(AssembleErrors::from_lines)
+}
+
+/// Monta as linhas geradas por um [`Preprocessor`](crate::Preprocessor). Os erros indicam o
+/// arquivo e a linha originais de cada linha e, nas linhas geradas por macros, o uso da macro.
+pub fn assemble_lines(lines: &[SourceLine]) -> Result<Vec<Word>, AssembleErrors> {
+    assemble_indexed(lines).map_err(AssembleErrors::from_lines)
+}
+
+/// Monta as linhas `lines`, retornando cada erro junto do índice da linha em que foi encontrado.
+pub(crate) fn assemble_indexed(
+    lines: &[SourceLine],
+) -> Result<Vec<Word>, Vec<(usize, AssembleError)>> {
+    let texts: Vec<&str> = lines.iter().map(|line| line.text.as_str()).collect();
+
+    assemble_from(&texts, |i, range| lines[i].span(range))
+}
+
+/// Monta as linhas `lines`, sendo `span` o trecho dos *bytes* dados de cada linha. Os erros são
+/// retornados junto do índice da linha em que foram encontrados.
+fn assemble_from(
+    lines: &[&str],
+    span: impl Fn(usize, Range<usize>) -> Span,
+) -> Result<Vec<Word>, Vec<(usize, AssembleError)>> {
+    let report = |errors: Vec<(usize, LineError)>| {
+        errors
+            .into_iter()
+            .map(|(source, LineError { kind, range })| {
+                let span = span(source, range);
+                (source, AssembleError { kind, span })
+            })
+            .collect()
+    };
+
+    let mut assembler = Assembler::new();
+    let mut errors = vec![];
+
+    for (i, &text) in lines.iter().enumerate() {
+        let line_errors = assembler.read_line(i, text);
+        errors.extend(line_errors.into_iter().map(|e| (i, e)));
+    }
+
+    if assembler.size > MEMORY_SIZE {
+        let last = lines.len() - 1;
+        let kind = AssembleErrorKind::ProgramTooLarge {
+            size: assembler.size,
+        };
+
+        errors.push((last, at(Part::line(lines[last]))(kind)));
+        return Err(report(errors));
+    }
+
+    let mut program = Vec::with_capacity(assembler.size);
+    let mut statics = vec![];
+
+    for statement in &assembler.statements {
+        let result = match &statement.kind {
+            StatementKind::Instruction {
+                instruction,
+                operands,
+            } => assembler
+                .encode(statement.text, *instruction, operands)
+                .map(|words| program.extend(words)),
+            StatementKind::Words(words) => {
+                program.extend(words);
+                Ok(())
+            }
+            StatementKind::Static { target, value } => assembler
+                .initialize(target, value)
+                .map(|initialization| statics.push(initialization)),
+        };
+
+        if let Err(e) = result {
+            errors.push((statement.source, e));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(report(errors));
+    }
+
+    // Inicializações fora do programa estendem a imagem com palavras zeradas.
+    for (address, value) in statics {
+        if address >= program.len() {
+            program.resize(address + 1, Word::ZERO);
+        }
+
+        program[address] = value;
+    }
+
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Listing;
+
+    #[test]
+    fn test_usage_syntax() {
+        let program = assemble(
+            "LOAD R3, 0xff00
+             LOADN R3, #0xff00
+             STOREN 0x00ff, #0b10100
+             MOV R3, SP
+             mov sp, r0
+             PUSH FR
+             POP R5
+             SHIFTL0 R7, 9
+             ADDC R3, R0, R7",
+        )
+        .unwrap();
+
+        let expected = [
+            0b110000_011_000_000_0,
+            0xff00,
+            0b111000_011_000_000_0,
+            0xff00,
+            0b111001_000_000_000_0,
+            0x00ff,
+            0b10100,
+            0b110011_011_000_000_1,
+            0b110011_000_000_000_1 | 0b10,
+            0b000101_000_100_000_0,
+            0b000110_101_000_000_0,
+            0b010000_111_000_100_1,
+            0b100000_011_000_111_1,
+        ]
+        .map(Word);
+
+        assert_eq!(program, expected);
+    }
+
+    #[test]
+    fn test_forward_labels_and_immediates() {
+        let program = assemble(
+            "    CALL sub
+                 HALT
+             sub: LOADN R0, #sub
+                  RTS",
+        )
+        .unwrap();
+
+        assert_eq!(program[1], Word(3));
+        assert_eq!(program[4], Word(3));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |source: &str| assemble(source).unwrap_err().into_iter().next().unwrap();
+
+        let undefined = error("NOP\nJMP fim");
+        assert_eq!(
+            undefined.kind,
+            AssembleErrorKind::UndefinedLabel {
+                label: "fim".to_string()
+            }
+        );
+        assert_eq!(
+            (
+                undefined.span.line,
+                undefined.span.column,
+                undefined.span.len
+            ),
+            (2, 5, 3)
+        );
+        assert_eq!(
+            error("a: NOP\na: NOP").kind,
+            AssembleErrorKind::DuplicateLabel {
+                label: "a".to_string()
+            }
+        );
+        assert_eq!(
+            error("ADD R1, R2").kind,
+            AssembleErrorKind::OperandCount {
+                instruction: Instruction::ADD,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            error("LOADN R1, 5").kind,
+            AssembleErrorKind::InvalidOperand {
+                instruction: Instruction::LOADN,
+                operand: "5".to_string()
+            }
+        );
+        assert!(matches!(
+            error("ADDD R1, R2, R3").kind,
+            AssembleErrorKind::UnknownMnemonic(_)
+        ));
+        assert_eq!(
+            error("LOADN R1, #70000").kind,
+            AssembleErrorKind::ValueOutOfRange { value: 70000 }
+        );
+    }
+
+    #[test]
+    fn test_multiple_errors() {
+        let errors = assemble(
+            "
+            x: LOADN R8, #1
+            x: ADD R1, R2
+               LOADN R1, #0x10000
+               STORE fim, R1
+               var #3
+            2x: ADX R9, #(1, R1",
+        )
+        .unwrap_err();
+
+        let found: Vec<(&str, usize, usize, usize)> = errors
+            .errors()
+            .iter()
+            .map(|e| (e.kind.name(), e.span.line, e.span.column, e.span.len))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                ("invalid_register", 2, 22, 2),
+                ("duplicate_label", 3, 13, 1),
+                ("operand_count", 3, 16, 10),
+                ("value_out_of_range", 4, 27, 7),
+                ("undefined_label", 5, 22, 3),
+                ("invalid_label", 7, 13, 2),
+                ("unknown_mnemonic", 7, 17, 3),
+                ("invalid_register", 7, 21, 2),
+                ("invalid_expression", 7, 25, 3),
+            ]
+        );
+        assert_eq!(
+            errors.to_json(Locale::English),
+            format!(
+                "[{}]",
+                errors
+                    .errors()
+                    .iter()
+                    .map(|e| e.to_json(Locale::English))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+        );
+        assert!(errors.to_string().contains(
+            "Linha 4, coluna 27: Valor não cabe em uma palavra: 65536\n\
+             4 |                LOADN R1, #0x10000\n  \
+               |                           ^^^^^^^"
+        ));
+    }
+
+    #[test]
+    fn test_directives() {
+        let program = assemble(
+            r#"
+                JMP main
+            msg: string "Oi; \"a\":b\n"
+            x:  var #2
+                static x + #1, #'A'
+                static x, #msg
+            main:
+                LOADN R0, #';'
+                HALT
+            "#,
+        )
+        .unwrap();
+
+        let msg: Vec<Word> = "Oi; \"a\":b\n\0".chars().map(|c| Word(c as u16)).collect();
+        assert_eq!(&program[2..13], &msg[..]);
+        assert_eq!(program[13..15], [Word(2), Word('A' as u16)]);
+        assert_eq!(program[1], Word(15));
+        assert_eq!(program[16], Word(';' as u16));
+    }
+
+    #[test]
+    fn test_directive_errors() {
+        let error = |source: &str| {
+            assemble(source)
+                .unwrap_err()
+                .into_iter()
+                .next()
+                .unwrap()
+                .kind
+        };
+
+        assert!(matches!(
+            error("var 3"),
+            AssembleErrorKind::InvalidDirective { .. }
+        ));
+        assert!(matches!(
+            error(r#"string "a"b""#),
+            AssembleErrorKind::InvalidDirective { .. }
+        ));
+        assert!(matches!(
+            error(r#"string "\q""#),
+            AssembleErrorKind::InvalidString { .. }
+        ));
+        assert!(matches!(
+            error("LOADN R0, #'ab'"),
+            AssembleErrorKind::InvalidCharacter { .. }
+        ));
+        assert_eq!(
+            error("var #-1"),
+            AssembleErrorKind::ValueOutOfRange { value: -1 }
+        );
+        assert_eq!(
+            error("var #0"),
+            AssembleErrorKind::ValueOutOfRange { value: 0 }
+        );
+        assert_eq!(
+            error("NOP\nvar #0x8000"),
+            AssembleErrorKind::ProgramTooLarge { size: 0x8001 }
+        );
+        assert_eq!(
+            error("static 0x7fff + #1, #0"),
+            AssembleErrorKind::AddressOutOfRange { address: 0x8000 }
+        );
+        assert_eq!(
+            assemble("static 3, #7").unwrap(),
+            vec![Word(0), Word(0), Word(0), Word(7)]
+        );
+    }
+
+    #[test]
+    fn test_constants_and_expressions() {
+        let program = assemble(
+            "
+                define AMARELO 2816
+                SCREEN_W equ 40
+            LINHA: EQU SCREEN_W * 2
+            loop:
+                LOADN R1, #LINHA + 1
+                LOADN R2, #'A' + AMARELO + (VERDE - AMARELO)
+                JMP loop + 2
+                var #SCREEN_W / 20
+            VERDE equ 512
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(program[1], Word(81));
+        assert_eq!(program[3], Word('A' as u16 + 512));
+        assert_eq!(program[5], Word(2));
+        assert_eq!(program.len(), 8);
+    }
+
+    #[test]
+    fn test_constant_chains() {
+        let mut source: String = (0..5000)
+            .map(|i| format!("C{i} equ C{} + 1\n", i + 1))
+            .collect();
+        source += "C5000 equ 0\nLOADN R0, #C0 & 0\nLOADN R1, #C0";
+        assert_eq!(
+            assemble(&source).unwrap()[1..],
+            [Word(0), Word(0xe080), Word(5000)]
+        );
+
+        // Cada constante é avaliada uma vez, mesmo quando usada várias vezes.
+        let mut source: String = (0..60)
+            .map(|i| format!("D{i} equ D{} + D{}\n", i + 1, i + 1))
+            .collect();
+        source += "D60 equ 0\nLOADN R0, #D0";
+        assert_eq!(assemble(&source).unwrap()[1], Word(0));
+    }
+
+    #[test]
+    fn test_constant_errors() {
+        let error = |source: &str| {
+            assemble(source)
+                .unwrap_err()
+                .into_iter()
+                .next()
+                .unwrap()
+                .kind
+        };
+
+        assert_eq!(
+            error("A equ B + 1\nB equ A\nLOADN R0, #A"),
+            AssembleErrorKind::CyclicConstant {
+                name: "A".to_string()
+            }
+        );
+        assert_eq!(
+            error("x: NOP\nvar #x"),
+            AssembleErrorKind::NotConstant {
+                text: "x".to_string()
+            }
+        );
+        assert_eq!(
+            error("N equ 1\nN: NOP"),
+            AssembleErrorKind::DuplicateLabel {
+                label: "N".to_string()
+            }
+        );
+
+        // A primeira definição é mantida, então `N` ainda cabe em uma palavra.
+        let errors = assemble("N equ 1\nN equ 70000\nLOADN R0, #N").unwrap_err();
+        assert_eq!(errors.errors().len(), 1);
+        assert_eq!(errors.errors()[0].span.line, 2);
+
+        assert_eq!(
+            error("LOADN R0, #1 / (2 - 2)"),
+            AssembleErrorKind::DivisionByZero
+        );
+        assert!(matches!(
+            error("R1 equ 3"),
+            AssembleErrorKind::InvalidLabel { .. }
+        ));
+        assert!(matches!(
+            error("LOADN R0, #(1 + 2"),
+            AssembleErrorKind::InvalidExpression { .. }
+        ));
+    }
+
+    #[test]
+    fn test_reassemble_listing() {
+        let source = r#"
+                CALL sub
+                JZ end
+            sub:
+                LOADN R1, #-1
+                ROTL R1, 3
+                RTS
+            end:
+                HALT
+            data:
+                string "fim \\ \"ok\""
+                var #3
+                static data + #12, #0x1234"#;
+
+        let program = assemble(source).unwrap();
+        let listing = Listing::new(&program).unwrap().to_string();
+
+        assert_eq!(assemble(&listing).unwrap(), program);
+    }
+}
@@ -0,0 +1,246 @@
+use std::ops::{Bound, RangeBounds};
+
+use thiserror::Error;
+
+use crate::locale::{key, message};
+use crate::{Locale, Localize};
+
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub enum BitsError {
+    OutOfRange {
+        start: usize,
+        end: usize,
+        width: usize,
+    },
+
+    ValueTooWide {
+        len: usize,
+    },
+}
+
+impl Localize for BitsError {
+    fn localize(&self, locale: Locale) -> String {
+        match self {
+            BitsError::OutOfRange { start, end, width } => message(
+                locale,
+                key::BITS_OUT_OF_RANGE,
+                &[("start", start), ("end", end), ("width", width)],
+            ),
+            BitsError::ValueTooWide { len } => {
+                message(locale, key::VALUE_TOO_WIDE, &[("len", len)])
+            }
+        }
+    }
+}
+
+/// Converte o intervalo `r` em um intervalo semiaberto `start..end` de um valor com `width`
+/// *bits*.
+fn field<R: RangeBounds<usize>>(r: &R, width: usize) -> Result<(usize, usize), BitsError> {
+    let start = match r.start_bound() {
+        Bound::Included(&n) => Some(n),
+        Bound::Excluded(&n) => n.checked_add(1),
+        Bound::Unbounded => Some(0),
+    };
+
+    let end = match r.end_bound() {
+        Bound::Included(&n) => n.checked_add(1),
+        Bound::Excluded(&n) => Some(n),
+        Bound::Unbounded => Some(width),
+    };
+
+    match (start, end) {
+        (Some(start), Some(end)) if start <= end && end <= width => Ok((start, end)),
+        (start, end) => Err(BitsError::OutOfRange {
+            start: start.unwrap_or(usize::MAX),
+            end: end.unwrap_or(usize::MAX),
+            width,
+        }),
+    }
+}
+
+/// Acesso a campos de *bits* de inteiros sem sinal.
+///
+/// A contagem dos *bits* começa do *low bit* para o *high bit*. Todos os tipos de intervalo são
+/// aceitos, inclusive os com início exclusivo ou sem limites.
+///
+/// ## Exemplo
+///
+/// ```
+/// use isa::*;
+///
+/// //bits:        543210
+/// let v: u16 = 0b101000;
+/// assert_eq!(0b101, v.bits(3..=5));
+/// assert_eq!(0b101, v.bits(3..));
+/// assert_eq!(0b101110, v.set_bits(0b11, 1..3));
+/// assert!(v.checked_set_bits(0b111, 1..3).is_err());
+/// ```
+pub trait Bits: Sized {
+    /// Retorna os *bits* no intervalo `r`.
+    ///
+    /// # Panics
+    /// Se o início do intervalo for maior que o fim ou se o intervalo exceder a largura do tipo.
+    fn bits<R: RangeBounds<usize>>(self, r: R) -> Self;
+
+    /// Retorna os *bits* no intervalo `r`, ou um erro caso o intervalo seja inválido.
+    fn checked_bits<R: RangeBounds<usize>>(self, r: R) -> Result<Self, BitsError>;
+
+    /// Altera os *bits* no intervalo `r` pelos *bits* de `b`. Os *bits* de `b` que não cabem no
+    /// intervalo são descartados.
+    ///
+    /// # Panics
+    /// Se o início do intervalo for maior que o fim ou se o intervalo exceder a largura do tipo.
+    fn set_bits<R: RangeBounds<usize>>(self, b: Self, r: R) -> Self;
+
+    /// Altera os *bits* no intervalo `r` pelos *bits* de `b`, ou retorna um erro caso o intervalo
+    /// seja inválido ou `b` não caiba nele.
+    fn checked_set_bits<R: RangeBounds<usize>>(self, b: Self, r: R) -> Result<Self, BitsError>;
+}
+
+macro_rules! impl_bits {
+    ($($t:ty),+) => {
+        $(impl Bits for $t {
+            fn bits<R: RangeBounds<usize>>(self, r: R) -> Self {
+                match self.checked_bits(r) {
+                    Ok(v) => v,
+                    Err(e) => panic!("{e}"),
+                }
+            }
+
+            fn checked_bits<R: RangeBounds<usize>>(self, r: R) -> Result<Self, BitsError> {
+                let (start, end) = field(&r, <$t>::BITS as usize)?;
+                let len = end - start;
+
+                if len == 0 {
+                    return Ok(0);
+                }
+
+                Ok((self >> start) & (<$t>::MAX >> (<$t>::BITS as usize - len)))
+            }
+
+            fn set_bits<R: RangeBounds<usize>>(self, b: Self, r: R) -> Self {
+                let (start, end) = match field(&r, <$t>::BITS as usize) {
+                    Ok(field) => field,
+                    Err(e) => panic!("{e}"),
+                };
+                let len = end - start;
+
+                if len == 0 {
+                    return self;
+                }
+
+                let mask = <$t>::MAX >> (<$t>::BITS as usize - len);
+                (self & !(mask << start)) | ((b & mask) << start)
+            }
+
+            fn checked_set_bits<R: RangeBounds<usize>>(self, b: Self, r: R) -> Result<Self, BitsError> {
+                let (start, end) = field(&r, <$t>::BITS as usize)?;
+                let len = end - start;
+
+                if len < <$t>::BITS as usize && b >> len != 0 {
+                    return Err(BitsError::ValueTooWide { len });
+                }
+
+                Ok(self.set_bits(b, start..end))
+            }
+        })+
+    };
+}
+
+impl_bits!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound::{self, Excluded, Included, Unbounded};
+
+    use super::*;
+
+    /// Implementação de referência, *bit* a *bit*, sobre o intervalo semiaberto `start..end`.
+    fn reference_bits(v: u8, start: usize, end: usize) -> u8 {
+        (start..end)
+            .rev()
+            .fold(0, |acc, i| (acc << 1) | ((v >> i) & 1))
+    }
+
+    fn reference_set_bits(v: u8, b: u8, start: usize, end: usize) -> u8 {
+        (start..end).fold(v, |acc, i| {
+            let bit = (b >> (i - start)) & 1;
+            (acc & !(1 << i)) | (bit << i)
+        })
+    }
+
+    type Range = (Bound<usize>, Bound<usize>);
+
+    /// Todas as combinações de limites para valores de 8 *bits*, junto do intervalo semiaberto
+    /// equivalente, quando válido.
+    fn all_bounds() -> Vec<(Range, Option<(usize, usize)>)> {
+        let mut bounds = vec![];
+
+        for s in 0..=9 {
+            for e in 0..=9 {
+                let starts = [(Included(s), s), (Excluded(s), s + 1), (Unbounded, 0)];
+                let ends = [(Included(e), e + 1), (Excluded(e), e), (Unbounded, 8)];
+
+                for &(start_bound, start) in &starts {
+                    for &(end_bound, end) in &ends {
+                        let expected = (start <= end && end <= 8).then_some((start, end));
+                        bounds.push(((start_bound, end_bound), expected));
+                    }
+                }
+            }
+        }
+
+        bounds
+    }
+
+    #[test]
+    fn test_bits_every_bound_kind() {
+        for (range, expected) in all_bounds() {
+            for v in 0..=u8::MAX {
+                match expected {
+                    Some((start, end)) => {
+                        assert_eq!(v.checked_bits(range), Ok(reference_bits(v, start, end)))
+                    }
+                    None => assert!(v.checked_bits(range).is_err(), "{range:?}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_bits_every_bound_kind() {
+        for (range, expected) in all_bounds() {
+            for v in (0..=u8::MAX).step_by(7) {
+                for b in 0..=u8::MAX {
+                    let Some((start, end)) = expected else {
+                        assert!(v.checked_set_bits(b, range).is_err(), "{range:?}");
+                        continue;
+                    };
+
+                    let fits = end - start == 8 || b >> (end - start) == 0;
+                    let reference = reference_set_bits(v, b, start, end);
+
+                    assert_eq!(v.set_bits(b, range), reference);
+                    assert_eq!(v.checked_set_bits(b, range).is_ok(), fits);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_full_width() {
+        assert_eq!(u16::MAX.bits(..), u16::MAX);
+        assert_eq!(0xabcd_u16.bits(8..), 0xab);
+        assert_eq!(0_u16.set_bits(0xffff, ..), 0xffff);
+        assert_eq!(usize::MAX.bits(..), usize::MAX);
+        assert_eq!(
+            0_u16.checked_bits(0..=16),
+            Err(BitsError::OutOfRange {
+                start: 0,
+                end: 17,
+                width: 16
+            })
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{Condition, DecodedInstruction, Instruction, Mov, Operands, Register, StackOperand};
+
+/// Categoria de uma instrução, como agrupada na Arquitetura do Processador ICMC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// Transferência de dados entre registradores e memória.
+    DataManipulation,
+    /// Comunicação com periféricos.
+    Peripheral,
+    /// Entrada e saída: teclado, tela e som.
+    Io,
+    /// Operações aritméticas entre registradores.
+    Arithmetic,
+    /// Operações lógicas, deslocamentos, rotações e comparação.
+    Logic,
+    /// Pulos, chamadas e retornos.
+    Branch,
+    /// Empilhamento e desempilhamento de valores na *stack*.
+    Stack,
+    /// Controle do processador e do *carry*.
+    Control,
+}
+
+impl Instruction {
+    /// Indica se a instrução pode alterar o `PC` para um endereço diferente da próxima
+    /// instrução: pulos (`J*`), chamadas (`C*`) e retornos (`RTS`, `RTI`).
+    pub fn is_branch(&self) -> bool {
+        self.condition().is_some() || self.is_return()
+    }
+
+    /// Indica se a instrução é uma chamada de procedimento (`CALL` e `C*`).
+    pub fn is_call(&self) -> bool {
+        self.condition().is_some() && self.opcode() == Instruction::CALL.opcode()
+    }
+
+    /// Indica se a instrução é um retorno de procedimento ou de interrupção.
+    pub fn is_return(&self) -> bool {
+        matches!(self, Instruction::RTS | Instruction::RTI)
+    }
+
+    /// Indica se a instrução depende do *flag register* para decidir se pula ou chama.
+    pub fn is_conditional(&self) -> bool {
+        !matches!(self.condition(), None | Some(Condition::Always))
+    }
+
+    /// Indica se a instrução lê a memória de dados, incluindo a *stack*.
+    pub fn reads_memory(&self) -> bool {
+        matches!(
+            self,
+            Instruction::LOAD
+                | Instruction::LOADI
+                | Instruction::RTS
+                | Instruction::RTI
+                | Instruction::POP
+        )
+    }
+
+    /// Indica se a instrução escreve na memória de dados, incluindo a *stack*.
+    pub fn writes_memory(&self) -> bool {
+        self.is_call()
+            || matches!(
+                self,
+                Instruction::STORE | Instruction::STOREN | Instruction::STOREI | Instruction::PUSH
+            )
+    }
+
+    /// Indica se a instrução pode alterar o *flag register*. Para [`Instruction::POP`], isso
+    /// só ocorre em `POP FR`; veja [`DecodedInstruction::registers_written`].
+    pub fn modifies_flags(&self) -> bool {
+        matches!(self.category(), Category::Arithmetic)
+            || matches!(
+                self,
+                Instruction::AND
+                    | Instruction::OR
+                    | Instruction::XOR
+                    | Instruction::NOT
+                    | Instruction::CMP
+                    | Instruction::CLEARC
+                    | Instruction::SETC
+                    | Instruction::POP
+            )
+    }
+
+    /// Indica se a instrução empilha ou desempilha valores da *stack*.
+    pub fn touches_stack(&self) -> bool {
+        self.is_call() || self.is_return() || matches!(self, Instruction::PUSH | Instruction::POP)
+    }
+}
+
+impl DecodedInstruction {
+    /// Retorna os registradores lidos pela instrução.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let addc = Instruction::decode(Word(0b100000_011_000_111_1)).unwrap(); // ADDC R3, R0, R7
+    ///
+    /// assert_eq!(vec![Register::R0, Register::R7, Register::FR], addc.registers_read());
+    /// assert_eq!(vec![Register::R3, Register::FR], addc.registers_written());
+    /// ```
+    pub fn registers_read(&self) -> Vec<Register> {
+        let mut read = match self.operands {
+            Operands::None => vec![],
+            Operands::Rx { rx } => match self.instruction {
+                Instruction::STORE | Instruction::SOUND | Instruction::INC | Instruction::DEC => {
+                    vec![rx]
+                }
+                _ => vec![],
+            },
+            Operands::RxRy { rx, ry } => match self.instruction {
+                Instruction::LOADI | Instruction::INPUT | Instruction::NOT => vec![ry],
+                _ => vec![rx, ry],
+            },
+            Operands::RxRyRz { ry, rz, .. } => vec![ry, rz],
+            Operands::Shift { rx, .. } => vec![rx],
+            Operands::Mov(Mov::Register { ry, .. }) => vec![ry],
+            Operands::Mov(Mov::FromSp { .. }) => vec![Register::SP],
+            Operands::Mov(Mov::ToSp { rx }) => vec![rx],
+            Operands::Stack(operand) => match (self.instruction, operand) {
+                (Instruction::PUSH, StackOperand::Register(rx)) => vec![rx],
+                (Instruction::PUSH, StackOperand::FlagRegister) => vec![Register::FR],
+                _ => vec![],
+            },
+        };
+
+        if matches!(self.instruction, Instruction::ADDC | Instruction::SUBC)
+            || self.instruction.is_conditional()
+        {
+            read.push(Register::FR);
+        }
+
+        if self.instruction.touches_stack() {
+            read.push(Register::SP);
+        }
+
+        if self.instruction.is_call() {
+            read.push(Register::PC);
+        }
+
+        read
+    }
+
+    /// Retorna os registradores escritos pela instrução.
+    pub fn registers_written(&self) -> Vec<Register> {
+        let mut written = match self.operands {
+            Operands::None => vec![],
+            Operands::Rx { rx } => match self.instruction {
+                Instruction::STORE | Instruction::SOUND => vec![],
+                _ => vec![rx],
+            },
+            Operands::RxRy { rx, .. } => match self.instruction {
+                Instruction::LOADI | Instruction::INPUT | Instruction::NOT => vec![rx],
+                _ => vec![],
+            },
+            Operands::RxRyRz { rx, .. } | Operands::Shift { rx, .. } => vec![rx],
+            Operands::Mov(Mov::Register { rx, .. } | Mov::FromSp { rx }) => vec![rx],
+            Operands::Mov(Mov::ToSp { .. }) => vec![Register::SP],
+            Operands::Stack(operand) => match (self.instruction, operand) {
+                (Instruction::POP, StackOperand::Register(rx)) => vec![rx],
+                (Instruction::POP, StackOperand::FlagRegister) => vec![Register::FR],
+                _ => vec![],
+            },
+        };
+
+        if self.instruction.modifies_flags() && self.instruction != Instruction::POP {
+            written.push(Register::FR);
+        }
+
+        if self.instruction.touches_stack() {
+            written.push(Register::SP);
+        }
+
+        if self.instruction.is_branch() {
+            written.push(Register::PC);
+        }
+
+        written
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Word;
+
+    #[test]
+    fn test_categories() {
+        assert_eq!(Instruction::LOADN.category(), Category::DataManipulation);
+        assert_eq!(Instruction::OUTCHAR.category(), Category::Io);
+        assert_eq!(Instruction::MOD.category(), Category::Arithmetic);
+        assert_eq!(Instruction::ROTR.category(), Category::Logic);
+        assert_eq!(Instruction::CN.category(), Category::Branch);
+        assert_eq!(Instruction::POP.category(), Category::Stack);
+        assert_eq!(Instruction::BREAKP.category(), Category::Control);
+    }
+
+    #[test]
+    fn test_predicates() {
+        assert!(Instruction::JMP.is_branch() && !Instruction::JMP.is_conditional());
+        assert!(Instruction::JEL.is_conditional() && !Instruction::JEL.is_call());
+        assert!(Instruction::CEQ.is_call() && Instruction::CEQ.writes_memory());
+        assert!(Instruction::RTI.is_branch() && Instruction::RTI.reads_memory());
+        assert!(Instruction::PUSH.touches_stack() && !Instruction::PUSH.is_branch());
+        assert!(Instruction::CMP.modifies_flags() && !Instruction::SHIFTL0.modifies_flags());
+        assert!(!Instruction::LOADN.reads_memory());
+    }
+
+    #[test]
+    fn test_registers() {
+        let pop_fr = Instruction::decode(Word(0b000110_000_100_000_0)).unwrap();
+        assert_eq!(pop_fr.registers_read(), vec![Register::SP]);
+        assert_eq!(pop_fr.registers_written(), vec![Register::FR, Register::SP]);
+
+        let mov = Instruction::decode(Word(0b110011_010_000_000_1)).unwrap(); // MOV R2, SP
+        assert_eq!(mov.registers_read(), vec![Register::SP]);
+        assert_eq!(mov.registers_written(), vec![Register::R2]);
+
+        let call = Instruction::decode(Word(0b000011_001_100_000_0)).unwrap(); // CZ
+        assert_eq!(
+            call.registers_read(),
+            vec![Register::FR, Register::SP, Register::PC]
+        );
+        assert_eq!(call.registers_written(), vec![Register::SP, Register::PC]);
+
+        let cmp = Instruction::decode(Word(0b010110_011_010_000_0)).unwrap(); // CMP R3, R2
+        assert_eq!(cmp.registers_read(), vec![Register::R3, Register::R2]);
+        assert_eq!(cmp.registers_written(), vec![Register::FR]);
+    }
+}
@@ -0,0 +1,594 @@
+use thiserror::Error;
+
+use crate::locale::{key, message};
+use crate::{
+    Bits, Instruction, InvalidInstruction, Locale, Localize, Register, UsizeDecodeError, Word,
+    WordOutOfRange,
+};
+
+/// Erro retornado ao decodificar a instrução de um endereço da memória, como em
+/// [`Instruction::decode_at`].
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub enum DecodeAtError {
+    /// O endereço está fora da memória, ou não cabe em uma palavra.
+    AddressOutOfRange {
+        address: usize,
+        len: usize,
+    },
+    InvalidInstruction(#[from] InvalidInstruction),
+}
+
+impl Localize for DecodeAtError {
+    fn localize(&self, locale: Locale) -> String {
+        match self {
+            DecodeAtError::AddressOutOfRange { address, len } => {
+                let address = format!("{address:#06x}");
+                message(
+                    locale,
+                    key::DECODE_AT_OUT_OF_RANGE,
+                    &[("address", &address), ("len", len)],
+                )
+            }
+            DecodeAtError::InvalidInstruction(e) => e.localize(locale),
+        }
+    }
+}
+
+/// Formato dos operandos de uma instrução.
+///
+/// Cada entrada de `instruction_set!` declara o seu formato, de modo que a extração dos
+/// operandos é derivada da mesma tabela que define os códigos e as máscaras. Os formatos com
+/// `END` ou `NR` ocupam palavras adicionais na memória, logo após a palavra da instrução.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OperandFormat {
+    /// Sem operandos.
+    None,
+    /// `Rx`.
+    Rx,
+    /// `Rx, Ry`.
+    RxRy,
+    /// `Rx, Ry, Rz`.
+    RxRyRz,
+    /// `Rx, N`.
+    Shift,
+    /// `Rx, Ry`, `Rx, SP` ou `SP, Rx`.
+    Mov,
+    /// `Rx` ou `FR`.
+    Stack,
+    /// `Rx, END`.
+    RxAddress,
+    /// `Rx, #NR`.
+    RxImmediate,
+    /// `END, Rx`.
+    AddressRx,
+    /// `END, #NR`.
+    AddressImmediate,
+    /// `END`.
+    Address,
+}
+
+impl OperandFormat {
+    /// Indica se a instrução é seguida por uma palavra com o endereço `END`.
+    pub fn has_address(&self) -> bool {
+        matches!(
+            self,
+            OperandFormat::RxAddress
+                | OperandFormat::AddressRx
+                | OperandFormat::AddressImmediate
+                | OperandFormat::Address
+        )
+    }
+
+    /// Indica se a instrução é seguida por uma palavra com o valor imediato `NR`.
+    pub fn has_immediate(&self) -> bool {
+        matches!(
+            self,
+            OperandFormat::RxImmediate | OperandFormat::AddressImmediate
+        )
+    }
+
+    /// Retorna a quantidade de palavras ocupadas na memória por uma instrução neste formato.
+    ///
+    /// O endereço `END` sempre precede o valor imediato `NR`.
+    pub fn size(&self) -> usize {
+        1 + self.has_address() as usize + self.has_immediate() as usize
+    }
+}
+
+/// Direção de uma instrução [`Instruction::MOV`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mov {
+    /// `MOV Rx, Ry`.
+    Register { rx: Register, ry: Register },
+    /// `MOV Rx, SP`.
+    FromSp { rx: Register },
+    /// `MOV SP, Rx`.
+    ToSp { rx: Register },
+}
+
+/// Operando de uma instrução [`Instruction::PUSH`] ou [`Instruction::POP`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StackOperand {
+    /// `PUSH Rx` ou `POP Rx`.
+    Register(Register),
+    /// `PUSH FR` ou `POP FR`.
+    FlagRegister,
+}
+
+/// Operandos codificados na palavra de uma instrução.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Operands {
+    /// A instrução não possui operandos na palavra.
+    None,
+    Rx {
+        rx: Register,
+    },
+    RxRy {
+        rx: Register,
+        ry: Register,
+    },
+    RxRyRz {
+        rx: Register,
+        ry: Register,
+        rz: Register,
+    },
+    /// Registrador e quantidade `N` de *bits* deslocados ou girados.
+    Shift {
+        rx: Register,
+        n: usize,
+    },
+    Mov(Mov),
+    Stack(StackOperand),
+}
+
+impl Operands {
+    /// Extrai da palavra `v` os operandos no formato `format`.
+    pub(crate) fn from_word(format: OperandFormat, v: Word) -> Operands {
+        let (rx, ry, rz) = (v.rx(), v.ry(), v.rz());
+
+        match format {
+            OperandFormat::None | OperandFormat::AddressImmediate | OperandFormat::Address => {
+                Operands::None
+            }
+            OperandFormat::Rx
+            | OperandFormat::RxAddress
+            | OperandFormat::RxImmediate
+            | OperandFormat::AddressRx => Operands::Rx { rx },
+            OperandFormat::RxRy => Operands::RxRy { rx, ry },
+            OperandFormat::RxRyRz => Operands::RxRyRz { rx, ry, rz },
+            OperandFormat::Shift => Operands::Shift {
+                rx,
+                n: usize::from(v.0.bits(0..=3)),
+            },
+            OperandFormat::Mov => Operands::Mov(match v.0.bits(0..=1) {
+                0b01 => Mov::FromSp { rx },
+                0b11 => Mov::ToSp { rx },
+                _ => Mov::Register { rx, ry },
+            }),
+            OperandFormat::Stack => Operands::Stack(match v.0.bits(6..=6) {
+                0 => StackOperand::Register(rx),
+                _ => StackOperand::FlagRegister,
+            }),
+        }
+    }
+}
+
+/// Uma [`Instruction`] acompanhada dos seus operandos.
+///
+/// Os campos `address` e `immediate` guardam o conteúdo das palavras que seguem a instrução
+/// na memória (`END` e `NR`), quando conhecidos.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DecodedInstruction {
+    pub instruction: Instruction,
+    pub operands: Operands,
+    pub address: Option<Word>,
+    pub immediate: Option<Word>,
+}
+
+impl DecodedInstruction {
+    /// Cria uma instrução com os operandos `operands` e sem palavras adicionais.
+    pub fn new(instruction: Instruction, operands: Operands) -> Self {
+        Self {
+            instruction,
+            operands,
+            address: None,
+            immediate: None,
+        }
+    }
+
+    /// Define o endereço `END` da instrução.
+    pub fn with_address(mut self, address: Word) -> Self {
+        self.address = Some(address);
+        self
+    }
+
+    /// Define o valor imediato `NR` da instrução.
+    pub fn with_immediate(mut self, immediate: Word) -> Self {
+        self.immediate = Some(immediate);
+        self
+    }
+
+    /// Versão de [`DecodedInstruction::with_address`] que recebe um `usize`.
+    #[deprecated(note = "use `DecodedInstruction::with_address` com uma `Word`")]
+    pub fn with_address_usize(self, address: usize) -> Result<Self, WordOutOfRange> {
+        Ok(self.with_address(Word::try_from(address)?))
+    }
+
+    /// Versão de [`DecodedInstruction::with_immediate`] que recebe um `usize`.
+    #[deprecated(note = "use `DecodedInstruction::with_immediate` com uma `Word`")]
+    pub fn with_immediate_usize(self, immediate: usize) -> Result<Self, WordOutOfRange> {
+        Ok(self.with_immediate(Word::try_from(immediate)?))
+    }
+}
+
+/// Modo de decodificação das palavras.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Rejeita palavras que não estão na codificação canônica da sua instrução, isto é, que
+    /// possuem *bits* setados fora dos campos usados (veja [`Instruction::canonicalize`]).
+    Strict,
+    /// Aceita qualquer palavra cujos *bits* fixos correspondam a uma instrução.
+    #[default]
+    Lenient,
+}
+
+impl DecodeMode {
+    /// Decodifica a palavra `v` neste modo.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let rts = Word(0b000100_101_000_000_0); // RTS com o campo Rx setado
+    ///
+    /// assert!(DecodeMode::Lenient.decode(rts).is_ok());
+    /// assert!(DecodeMode::Strict.decode(rts).is_err());
+    /// ```
+    pub fn decode(&self, v: Word) -> Result<DecodedInstruction, InvalidInstruction> {
+        let decoded = Instruction::decode(v)?;
+
+        if *self == DecodeMode::Strict && decoded.encode_word() != Ok(v) {
+            return Err(InvalidInstruction::new(v));
+        }
+
+        Ok(decoded)
+    }
+
+    /// Decodifica a instrução no início de `mem` neste modo. Veja [`Instruction::decode_from`].
+    ///
+    /// # Panics
+    /// Se `mem` estiver vazio.
+    pub fn decode_from(&self, mem: &[Word]) -> Result<DecodedInstruction, InvalidInstruction> {
+        let mut decoded = self.decode(mem[0])?;
+        let format = decoded.instruction.format();
+        let mut trailing = mem.iter().skip(1).copied();
+
+        if format.has_address() {
+            decoded.address = trailing.next();
+        }
+
+        if format.has_immediate() {
+            decoded.immediate = trailing.next();
+        }
+
+        Ok(decoded)
+    }
+
+    /// Decodifica a instrução no endereço `address` de `mem` neste modo. Veja
+    /// [`Instruction::decode_at`].
+    pub fn decode_at(
+        &self,
+        mem: &[Word],
+        address: usize,
+    ) -> Result<DecodedInstruction, DecodeAtError> {
+        let word = u16::try_from(address).ok().filter(|_| address < mem.len());
+        let Some(word) = word else {
+            return Err(DecodeAtError::AddressOutOfRange {
+                address,
+                len: mem.len(),
+            });
+        };
+
+        Ok(self
+            .decode_from(&mem[address..])
+            .map_err(|e| e.at(Word(word)))?)
+    }
+}
+
+impl Instruction {
+    /// Decodifica a palavra `v`, retornando a [`Instruction`] e os seus operandos.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let mem = Word(0b100000_011_000_111_1); // ADDC R3, R0, R7
+    /// let decoded = Instruction::decode(mem).unwrap();
+    ///
+    /// assert_eq!(Instruction::ADDC, decoded.instruction);
+    /// assert_eq!(Operands::RxRyRz { rx: Register::R3, ry: Register::R0, rz: Register::R7 }, decoded.operands);
+    /// ```
+    pub fn decode(v: Word) -> Result<DecodedInstruction, InvalidInstruction> {
+        let instruction = Instruction::get_instruction(v)?;
+
+        Ok(DecodedInstruction::new(
+            instruction,
+            Operands::from_word(instruction.format(), v),
+        ))
+    }
+
+    /// Decodifica a instrução no início de `mem`, lendo também as palavras de endereço `END` e
+    /// valor imediato `NR` que a seguem. Caso `mem` termine antes dessas palavras, os campos
+    /// correspondentes ficam como [`None`].
+    ///
+    /// # Panics
+    /// Se `mem` estiver vazio.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let mem = [Word(0b111000_011_000_000_0), Word(0xff00), Word(0)]; // LOADN R3, #0xff00; NOP
+    /// let decoded = Instruction::decode_from(&mem).unwrap();
+    ///
+    /// assert_eq!(Some(Word(0xff00)), decoded.immediate);
+    /// assert_eq!(2, decoded.instruction.size());
+    /// ```
+    pub fn decode_from(mem: &[Word]) -> Result<DecodedInstruction, InvalidInstruction> {
+        DecodeMode::Lenient.decode_from(mem)
+    }
+
+    /// Versão de [`Instruction::decode`] que recebe um `usize`. Valores maiores que uma palavra
+    /// retornam [`UsizeDecodeError::WordOutOfRange`].
+    #[deprecated(note = "use `Instruction::decode` com uma `Word`")]
+    pub fn decode_usize(v: usize) -> Result<DecodedInstruction, UsizeDecodeError> {
+        Ok(Instruction::decode(Word::try_from(v)?)?)
+    }
+
+    /// Versão de [`Instruction::decode_from`] que recebe palavras `usize`. Apenas as palavras
+    /// usadas pela instrução precisam caber em uma palavra.
+    ///
+    /// # Panics
+    /// Se `mem` estiver vazio.
+    #[deprecated(note = "use `Instruction::decode_from` com palavras `Word`")]
+    pub fn decode_from_usize(mem: &[usize]) -> Result<DecodedInstruction, UsizeDecodeError> {
+        let mut decoded = Instruction::decode(Word::try_from(mem[0])?)?;
+        let format = decoded.instruction.format();
+        let mut trailing = mem.iter().skip(1).map(|&v| Word::try_from(v));
+
+        if format.has_address() {
+            decoded.address = trailing.next().transpose()?;
+        }
+
+        if format.has_immediate() {
+            decoded.immediate = trailing.next().transpose()?;
+        }
+
+        Ok(decoded)
+    }
+
+    /// Decodifica a instrução no endereço `address` de `mem`, da mesma forma que
+    /// [`Instruction::decode_from`]. Em caso de erro, o endereço é informado no
+    /// [`InvalidInstruction`]. Endereços fora de `mem` retornam
+    /// [`DecodeAtError::AddressOutOfRange`].
+    pub fn decode_at(mem: &[Word], address: usize) -> Result<DecodedInstruction, DecodeAtError> {
+        DecodeMode::Lenient.decode_at(mem, address)
+    }
+
+    /// Reescreve a palavra `v` na codificação canônica da sua instrução, zerando os *bits* que
+    /// não são usados nem pela instrução nem pelos seus operandos.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let nop = Word(0b000000_000_000_101_1);
+    /// assert_eq!(Word(0), Instruction::canonicalize(nop).unwrap());
+    ///
+    /// let push_fr = Word(0b000101_011_100_000_0);
+    /// assert_eq!(Word(0b000101_000_100_000_0), Instruction::canonicalize(push_fr).unwrap());
+    /// ```
+    pub fn canonicalize(v: Word) -> Result<Word, InvalidInstruction> {
+        let decoded = Instruction::decode(v)?;
+
+        // Os operandos decodificados sempre cabem nos seus campos.
+        Ok(decoded
+            .encode_word()
+            .expect("operandos decodificados devem ser codificáveis"))
+    }
+
+    /// Retorna a quantidade de palavras ocupadas pela instrução na memória, incluindo o
+    /// endereço `END` e o valor imediato `NR`.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// assert_eq!(1, Instruction::ADD.size());
+    /// assert_eq!(2, Instruction::JMP.size());
+    /// assert_eq!(3, Instruction::STOREN.size());
+    /// ```
+    pub fn size(&self) -> usize {
+        self.format().size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walk_memory() {
+        let mem = [
+            0b111001_000_000_000_0, // STOREN 0x00ff, #0b10100
+            0x00ff,
+            0b10100,
+            0b000011_000_000_000_0, // CALL 0x003C
+            0x003c,
+            0b001111_000_000_000_0, // HALT
+        ]
+        .map(Word);
+
+        let mut pc = 0;
+        let mut decoded = vec![];
+        while pc < mem.len() {
+            let instruction = Instruction::decode_from(&mem[pc..]).unwrap();
+            pc += instruction.instruction.size();
+            decoded.push(instruction);
+        }
+
+        assert_eq!(
+            decoded,
+            vec![
+                DecodedInstruction::new(Instruction::STOREN, Operands::None)
+                    .with_address(Word(0x00ff))
+                    .with_immediate(Word(0b10100)),
+                DecodedInstruction::new(Instruction::CALL, Operands::None)
+                    .with_address(Word(0x003c)),
+                DecodedInstruction::new(Instruction::HALT, Operands::None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strict_mode() {
+        let canonical = [
+            0b000000_000_000_000_0, // NOP
+            0b110011_011_000_000_1, // MOV R3, SP
+            0b010000_110_100_001_0, // ROTL R6, 2
+            0b000010_001_100_000_0, // JZ
+        ]
+        .map(Word);
+
+        for v in canonical {
+            assert_eq!(DecodeMode::Strict.decode(v), DecodeMode::Lenient.decode(v));
+            assert_eq!(Instruction::canonicalize(v), Ok(v));
+        }
+
+        let non_canonical = [
+            (0b000000_000_000_000_1, 0b000000_000_000_000_0), // NOP
+            (0b110011_011_101_000_1, 0b110011_011_000_000_1), // MOV R3, SP
+            (0b010000_110_101_001_0, 0b010000_110_100_001_0), // ROTL R6, 2
+            (0b000010_001_110_010_0, 0b000010_001_100_000_0), // JZ
+            (0b000111_000_000_000_0, 0b000111_000_000_000_0), // Inválida
+        ]
+        .map(|(v, c)| (Word(v), Word(c)));
+
+        for (v, c) in non_canonical {
+            assert!(DecodeMode::Strict.decode(v).is_err());
+            assert_eq!(
+                Instruction::canonicalize(v).ok(),
+                DecodeMode::Lenient.decode(v).ok().map(|_| c)
+            );
+        }
+
+        let mem = [Word(0), Word(0b001111_000_000_001_0)]; // NOP; HALT com bits extras
+        assert_eq!(
+            DecodeMode::Strict.decode_at(&mem, 1),
+            Err(InvalidInstruction::new(mem[1]).at(Word(1)).into())
+        );
+    }
+
+    #[test]
+    fn test_decode_at_out_of_range() {
+        let mem = [Word(0), Word(0b001111_000_000_000_0)]; // NOP; HALT
+        assert!(Instruction::decode_at(&mem, 1).is_ok());
+        assert_eq!(
+            Instruction::decode_at(&mem, 2),
+            Err(DecodeAtError::AddressOutOfRange { address: 2, len: 2 })
+        );
+
+        let mem = vec![Word(0); 0x10001];
+        let err = Instruction::decode_at(&mem, 0x10000).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeAtError::AddressOutOfRange {
+                address: 0x10000,
+                len: 0x10001
+            }
+        );
+        assert_eq!(
+            "Address out of memory: 0x10000 (the memory has 65537 words)",
+            err.localize(Locale::English)
+        );
+    }
+
+    #[test]
+    fn test_decode_shift() {
+        let code = Word(0b010000_110_100_001_0); // ROTL R6, 2
+        assert_eq!(
+            Instruction::decode(code).unwrap(),
+            DecodedInstruction::new(
+                Instruction::ROTL,
+                Operands::Shift {
+                    rx: Register::R6,
+                    n: 2
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_decode_mov() {
+        let to_sp = Word(0b110011_000_000_001_1);
+        assert_eq!(
+            Instruction::decode(to_sp).unwrap().operands,
+            Operands::Mov(Mov::ToSp { rx: Register::R0 })
+        );
+
+        let from_sp = Word(0b110011_011_000_000_1);
+        assert_eq!(
+            Instruction::decode(from_sp).unwrap().operands,
+            Operands::Mov(Mov::FromSp { rx: Register::R3 })
+        );
+
+        let register = Word(0b110011_011_000_000_0);
+        assert_eq!(
+            Instruction::decode(register).unwrap().operands,
+            Operands::Mov(Mov::Register {
+                rx: Register::R3,
+                ry: Register::R0
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_stack() {
+        let push_fr = Word(0b000101_000_100_000_0);
+        assert_eq!(
+            Instruction::decode(push_fr).unwrap().operands,
+            Operands::Stack(StackOperand::FlagRegister)
+        );
+
+        let pop_r5 = Word(0b000110_101_000_000_0);
+        assert_eq!(
+            Instruction::decode(pop_r5).unwrap().operands,
+            Operands::Stack(StackOperand::Register(Register::R5))
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_decode_usize() {
+        let loadn = 0b111000_011_000_000_0; // LOADN R3, #NR
+        let decoded = Instruction::decode_from_usize(&[loadn, 5, 0x10000]).unwrap();
+        assert_eq!(decoded.immediate, Some(Word(5)));
+
+        assert_eq!(
+            Instruction::decode_from_usize(&[loadn, 0x10000]),
+            Err(UsizeDecodeError::WordOutOfRange(WordOutOfRange {
+                value: 0x10000
+            }))
+        );
+        assert!(Instruction::decode_usize(0x1_0000 | loadn).is_err());
+        assert!(DecodedInstruction::new(Instruction::NOP, Operands::None)
+            .with_immediate_usize(0x10000)
+            .is_err());
+    }
+}
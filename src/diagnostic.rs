@@ -0,0 +1,498 @@
+use std::fmt;
+use std::ops::{Deref, Range};
+
+use thiserror::Error;
+
+use crate::locale::{key, message};
+use crate::{AssembleError, AssembleErrorKind, Locale, Localize};
+
+/// Trecho do código-fonte em que um erro foi encontrado: `len` caracteres a partir da coluna
+/// `column` da linha `line` do arquivo `file`. Linhas e colunas começam em 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// O arquivo, ou [`None`] quando o código-fonte é montado diretamente por
+    /// [`assemble`](crate::assemble).
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+    /// Texto da linha. Em linhas geradas por macros, é o texto já expandido.
+    pub text: String,
+    /// Trecho em que foi usada a macro que gerou a linha, quando o erro está no corpo da macro.
+    /// Erros nos argumentos apontam diretamente para o uso da macro.
+    pub expansion: Option<Box<Span>>,
+}
+
+impl Span {
+    /// Cria o trecho da linha `text` que ocupa os *bytes* `range`.
+    pub(crate) fn new(file: Option<&str>, line: usize, text: &str, range: Range<usize>) -> Self {
+        Self {
+            file: file.map(str::to_string),
+            line,
+            column: text[..range.start].chars().count() + 1,
+            len: text[range].chars().count().max(1),
+            text: text.to_string(),
+            expansion: None,
+        }
+    }
+
+    /// Retorna a linha do trecho, precedida do seu número, com `^` embaixo de cada caractere do
+    /// trecho.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let errors = assemble("NOP\nLOADN R1, #fim").unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     "2 | LOADN R1, #fim\n  |            ^^^",
+    ///     errors.errors()[0].span.snippet()
+    /// );
+    /// ```
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let text = self.text.replace('\t', " ");
+
+        format!(
+            "{number} | {}\n{} | {}{}",
+            text.trim_end(),
+            " ".repeat(number.len()),
+            " ".repeat(self.column - 1),
+            "^".repeat(self.len)
+        )
+    }
+}
+
+/// Parte de uma linha do código-fonte, que guarda a posição, em *bytes*, em que começa na linha.
+/// As operações que extraem partes menores atualizam essa posição, para que os erros apontem
+/// para o trecho correto.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Part<'a> {
+    text: &'a str,
+    start: usize,
+}
+
+impl<'a> Part<'a> {
+    /// Cria a parte com a linha inteira.
+    pub(crate) fn line(text: &'a str) -> Self {
+        Self { text, start: 0 }
+    }
+
+    pub(crate) fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Retorna os *bytes* da linha ocupados pela parte.
+    pub(crate) fn range(&self) -> Range<usize> {
+        self.start..self.start + self.text.len()
+    }
+
+    /// Retorna a parte formada pelos *bytes* `range`, contados a partir do início desta parte.
+    pub(crate) fn slice(&self, range: Range<usize>) -> Self {
+        Self {
+            text: &self.text[range.clone()],
+            start: self.start + range.start,
+        }
+    }
+
+    /// Separa a parte antes e a parte depois do caractere na posição `i`.
+    pub(crate) fn split_around(&self, i: usize) -> (Self, Self) {
+        let c = self.text[i..].chars().next().map_or(0, char::len_utf8);
+        (self.slice(0..i), self.slice(i + c..self.text.len()))
+    }
+
+    pub(crate) fn trim(&self) -> Self {
+        self.trim_start().trim_end()
+    }
+
+    pub(crate) fn trim_start(&self) -> Self {
+        let start = self.text.len() - self.text.trim_start().len();
+        self.slice(start..self.text.len())
+    }
+
+    pub(crate) fn trim_end(&self) -> Self {
+        self.slice(0..self.text.trim_end().len())
+    }
+
+    pub(crate) fn strip_prefix(&self, prefix: char) -> Option<Self> {
+        self.text
+            .starts_with(prefix)
+            .then(|| self.slice(prefix.len_utf8()..self.text.len()))
+    }
+}
+
+impl fmt::Display for Part<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+impl Deref for Part<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
+
+/// Descreve a posição do trecho, como `main.asm, linha 3, coluna 7`.
+fn location(span: &Span, locale: Locale) -> String {
+    let (line, column) = (&span.line, &span.column);
+
+    match &span.file {
+        Some(file) => message(
+            locale,
+            key::LOCATION_FILE,
+            &[("file", file), ("line", line), ("column", column)],
+        ),
+        None => message(locale, key::LOCATION, &[("line", line), ("column", column)]),
+    }
+}
+
+/// Todos os erros encontrados na montagem de um programa, em ordem. Após um erro, a montagem
+/// continua na próxima linha, para que os demais erros também sejam encontrados.
+///
+/// ## Exemplo
+///
+/// ```
+/// use isa::*;
+///
+/// let errors = assemble("ADDD R1, R2, R3\nLOADN R1, 5\nJMP fim").unwrap_err();
+///
+/// assert_eq!(3, errors.errors().len());
+/// assert_eq!(
+///     "Linha 2, coluna 11: Operando inválido para a instrução LOADN: 5
+/// 2 | LOADN R1, 5
+///   |           ^",
+///     errors.to_string().split("\n\n").nth(1).unwrap()
+/// );
+/// assert_eq!(
+///     "{\"file\":null,\"line\":3,\"column\":5,\"length\":3,\"expansion\":null,\
+///      \"kind\":\"undefined_label\",\"message\":\"Undefined label: fim\"}",
+///     errors.errors()[2].to_json(Locale::English)
+/// );
+/// ```
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub struct AssembleErrors {
+    errors: Vec<AssembleError>,
+}
+
+impl AssembleErrors {
+    pub(crate) fn new(errors: Vec<AssembleError>) -> Self {
+        Self { errors }
+    }
+
+    /// Cria a lista a partir de cada erro e do índice da linha em que foi encontrado, ordenando
+    /// os erros pela linha. Erros da mesma linha mantêm a ordem em que foram encontrados.
+    pub(crate) fn from_lines(mut errors: Vec<(usize, AssembleError)>) -> Self {
+        errors.sort_by_key(|&(line, _)| line);
+        Self::new(errors.into_iter().map(|(_, error)| error).collect())
+    }
+
+    /// Retorna os erros, na ordem em que aparecem no código-fonte.
+    pub fn errors(&self) -> &[AssembleError] {
+        &self.errors
+    }
+
+    /// Retorna um *array* JSON com um objeto por erro, no formato de
+    /// [`AssembleError::to_json`].
+    pub fn to_json(&self, locale: Locale) -> String {
+        let errors: Vec<String> = self.errors.iter().map(|e| e.to_json(locale)).collect();
+        format!("[{}]", errors.join(","))
+    }
+}
+
+impl Localize for AssembleErrors {
+    /// Escreve cada erro seguido do trecho do código-fonte em que foi encontrado.
+    fn localize(&self, locale: Locale) -> String {
+        let reports: Vec<String> = self
+            .errors
+            .iter()
+            .map(|error| {
+                let mut report = format!("{}\n{}", error.localize(locale), error.span.snippet());
+                let mut span = &error.span;
+
+                while let Some(expansion) = &span.expansion {
+                    let location = location(expansion, locale);
+                    report += "\n";
+                    report += &message(locale, key::EXPANSION, &[("location", &location)]);
+                    report += "\n";
+                    report += &expansion.snippet();
+                    span = expansion;
+                }
+
+                report
+            })
+            .collect();
+
+        reports.join("\n\n")
+    }
+}
+
+impl IntoIterator for AssembleErrors {
+    type Item = AssembleError;
+    type IntoIter = std::vec::IntoIter<AssembleError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a AssembleErrors {
+    type Item = &'a AssembleError;
+    type IntoIter = std::slice::Iter<'a, AssembleError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.iter()
+    }
+}
+
+/// Escreve `text` como uma *string* JSON.
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+
+    for c in text.chars() {
+        match c {
+            '"' => json += "\\\"",
+            '\\' => json += "\\\\",
+            '\n' => json += "\\n",
+            '\r' => json += "\\r",
+            '\t' => json += "\\t",
+            c if u32::from(c) < 0x20 => json += &format!("\\u{:04x}", u32::from(c)),
+            c => json.push(c),
+        }
+    }
+
+    json + "\""
+}
+
+/// Escreve os campos JSON `file`, `line`, `column`, `length` e `expansion` do trecho `span`.
+fn json_span(span: &Span) -> String {
+    let file = match &span.file {
+        Some(file) => json_string(file),
+        None => "null".to_string(),
+    };
+
+    let expansion = match &span.expansion {
+        Some(expansion) => format!("{{{}}}", json_span(expansion)),
+        None => "null".to_string(),
+    };
+
+    format!(
+        "\"file\":{file},\"line\":{},\"column\":{},\"length\":{},\"expansion\":{expansion}",
+        span.line, span.column, span.len
+    )
+}
+
+impl AssembleError {
+    /// Retorna um objeto JSON com os campos `file` (`null` quando não há arquivo), `line`,
+    /// `column`, `length`, `expansion` (um objeto com os mesmos campos de posição, apontando
+    /// para o uso da macro que gerou a linha, ou `null`), `kind` (o tipo do erro, como
+    /// `undefined_label`) e `message` (a mensagem no idioma `locale`, sem a posição).
+    pub fn to_json(&self, locale: Locale) -> String {
+        format!(
+            "{{{},\"kind\":{},\"message\":{}}}",
+            json_span(&self.span),
+            json_string(self.kind.name()),
+            json_string(&self.kind.localize(locale))
+        )
+    }
+}
+
+impl Localize for AssembleError {
+    fn localize(&self, locale: Locale) -> String {
+        format!(
+            "{}: {}",
+            location(&self.span, locale),
+            self.kind.localize(locale)
+        )
+    }
+}
+
+impl AssembleErrorKind {
+    /// Retorna o nome do tipo do erro, como `undefined_label`, que não depende do idioma.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AssembleErrorKind::UnknownMnemonic(_) => "unknown_mnemonic",
+            AssembleErrorKind::InvalidRegister(_) => "invalid_register",
+            AssembleErrorKind::OperandCount { .. } => "operand_count",
+            AssembleErrorKind::InvalidOperand { .. } => "invalid_operand",
+            AssembleErrorKind::InvalidNumber { .. } => "invalid_number",
+            AssembleErrorKind::ValueOutOfRange { .. } => "value_out_of_range",
+            AssembleErrorKind::InvalidExpression { .. } => "invalid_expression",
+            AssembleErrorKind::ExpressionTooDeep { .. } => "expression_too_deep",
+            AssembleErrorKind::DivisionByZero => "division_by_zero",
+            AssembleErrorKind::NotConstant { .. } => "not_constant",
+            AssembleErrorKind::CyclicConstant { .. } => "cyclic_constant",
+            AssembleErrorKind::InvalidCharacter { .. } => "invalid_character",
+            AssembleErrorKind::InvalidString { .. } => "invalid_string",
+            AssembleErrorKind::InvalidDirective { .. } => "invalid_directive",
+            AssembleErrorKind::AddressOutOfRange { .. } => "address_out_of_range",
+            AssembleErrorKind::InvalidLabel { .. } => "invalid_label",
+            AssembleErrorKind::DuplicateLabel { .. } => "duplicate_label",
+            AssembleErrorKind::UndefinedLabel { .. } => "undefined_label",
+            AssembleErrorKind::Encode(_) => "encode",
+            AssembleErrorKind::Include { .. } => "include",
+            AssembleErrorKind::RecursiveInclude { .. } => "recursive_include",
+            AssembleErrorKind::DuplicateMacro { .. } => "duplicate_macro",
+            AssembleErrorKind::MacroArgumentCount { .. } => "macro_argument_count",
+            AssembleErrorKind::RecursiveMacro { .. } => "recursive_macro",
+            AssembleErrorKind::MacroExpansionTooLarge { .. } => "macro_expansion_too_large",
+            AssembleErrorKind::UnexpectedDirective { .. } => "unexpected_directive",
+            AssembleErrorKind::UnterminatedBlock { .. } => "unterminated_block",
+            AssembleErrorKind::ProgramTooLarge { .. } => "program_too_large",
+        }
+    }
+}
+
+impl Localize for AssembleErrorKind {
+    fn localize(&self, locale: Locale) -> String {
+        match self {
+            AssembleErrorKind::UnknownMnemonic(e) => e.localize(locale),
+            AssembleErrorKind::InvalidRegister(e) => e.localize(locale),
+            AssembleErrorKind::Encode(e) => e.localize(locale),
+            AssembleErrorKind::OperandCount {
+                instruction,
+                expected,
+                found,
+            } => message(
+                locale,
+                key::OPERAND_COUNT,
+                &[
+                    ("instruction", instruction),
+                    ("expected", expected),
+                    ("found", found),
+                ],
+            ),
+            AssembleErrorKind::InvalidOperand {
+                instruction,
+                operand,
+            } => message(
+                locale,
+                key::INVALID_OPERAND,
+                &[("instruction", instruction), ("operand", operand)],
+            ),
+            AssembleErrorKind::InvalidNumber { text } => {
+                message(locale, key::INVALID_NUMBER, &[("text", text)])
+            }
+            AssembleErrorKind::InvalidExpression { text } => {
+                message(locale, key::INVALID_EXPRESSION, &[("text", text)])
+            }
+            AssembleErrorKind::ExpressionTooDeep { text } => {
+                message(locale, key::EXPRESSION_TOO_DEEP, &[("text", text)])
+            }
+            AssembleErrorKind::NotConstant { text } => {
+                message(locale, key::NOT_CONSTANT, &[("text", text)])
+            }
+            AssembleErrorKind::InvalidCharacter { text } => {
+                message(locale, key::INVALID_CHARACTER, &[("text", text)])
+            }
+            AssembleErrorKind::InvalidString { text } => {
+                message(locale, key::INVALID_STRING, &[("text", text)])
+            }
+            AssembleErrorKind::ValueOutOfRange { value } => {
+                message(locale, key::VALUE_OUT_OF_RANGE, &[("value", value)])
+            }
+            AssembleErrorKind::DivisionByZero => message(locale, key::DIVISION_BY_ZERO, &[]),
+            AssembleErrorKind::CyclicConstant { name } => {
+                message(locale, key::CYCLIC_CONSTANT, &[("name", name)])
+            }
+            AssembleErrorKind::DuplicateMacro { name } => {
+                message(locale, key::DUPLICATE_MACRO, &[("name", name)])
+            }
+            AssembleErrorKind::RecursiveMacro { name } => {
+                message(locale, key::RECURSIVE_MACRO, &[("name", name)])
+            }
+            AssembleErrorKind::MacroExpansionTooLarge { name, lines } => message(
+                locale,
+                key::MACRO_EXPANSION_TOO_LARGE,
+                &[("name", name), ("lines", lines)],
+            ),
+            AssembleErrorKind::InvalidDirective {
+                directive,
+                operands,
+            } => message(
+                locale,
+                key::INVALID_DIRECTIVE,
+                &[("directive", directive), ("operands", operands)],
+            ),
+            AssembleErrorKind::AddressOutOfRange { address } => {
+                message(locale, key::ADDRESS_OUT_OF_RANGE, &[("address", address)])
+            }
+            AssembleErrorKind::InvalidLabel { label } => {
+                message(locale, key::INVALID_LABEL, &[("label", label)])
+            }
+            AssembleErrorKind::DuplicateLabel { label } => {
+                message(locale, key::DUPLICATE_LABEL, &[("label", label)])
+            }
+            AssembleErrorKind::UndefinedLabel { label } => {
+                message(locale, key::UNDEFINED_LABEL, &[("label", label)])
+            }
+            AssembleErrorKind::Include {
+                path,
+                message: reason,
+            } => message(locale, key::INCLUDE, &[("path", path), ("message", reason)]),
+            AssembleErrorKind::RecursiveInclude { path } => {
+                message(locale, key::RECURSIVE_INCLUDE, &[("path", path)])
+            }
+            AssembleErrorKind::MacroArgumentCount {
+                name,
+                expected,
+                found,
+            } => message(
+                locale,
+                key::MACRO_ARGUMENT_COUNT,
+                &[("name", name), ("expected", expected), ("found", found)],
+            ),
+            AssembleErrorKind::UnexpectedDirective { directive } => message(
+                locale,
+                key::UNEXPECTED_DIRECTIVE,
+                &[("directive", directive)],
+            ),
+            AssembleErrorKind::UnterminatedBlock { directive } => {
+                message(locale, key::UNTERMINATED_BLOCK, &[("directive", directive)])
+            }
+            AssembleErrorKind::ProgramTooLarge { size } => {
+                message(locale, key::PROGRAM_TOO_LARGE, &[("size", size)])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_columns() {
+        let text = "\tLOADN R1, #'ç' + x ; comentário";
+        let part = Part::line(text)
+            .slice(11..21)
+            .strip_prefix('#')
+            .unwrap()
+            .trim();
+        let span = Span::new(Some("a.asm"), 7, text, part.range());
+
+        assert_eq!(part.as_str(), "'ç' + x");
+        assert_eq!((span.column, span.len), (13, 7));
+        assert_eq!(
+            span.snippet(),
+            "7 |  LOADN R1, #'ç' + x ; comentário\n  |             ^^^^^^^"
+        );
+
+        let (word, rest) = Part::line(text).trim().split_around(5);
+        assert_eq!((word.range(), rest.trim().range()), (1..6, 7..text.len()));
+        assert_eq!(Span::new(None, 1, text, 8..8).len, 1);
+    }
+
+    #[test]
+    fn test_json_escapes() {
+        assert_eq!(
+            json_string("a\"b\\c\nd\u{1}é"),
+            "\"a\\\"b\\\\c\\nd\\u0001é\""
+        );
+    }
+}
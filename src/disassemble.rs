@@ -0,0 +1,161 @@
+use crate::{DecodeMode, DecodedInstruction, Word, MEMORY_SIZE};
+
+/// Conteúdo de uma posição da memória encontrado pelo [`Disassembler`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Disassembled {
+    /// Uma instrução, junto das palavras de endereço e valor imediato que a seguem.
+    Instruction(DecodedInstruction),
+    /// Uma palavra que não pôde ser decodificada como instrução.
+    Data(Word),
+}
+
+impl Disassembled {
+    /// Retorna a quantidade de palavras ocupadas na memória.
+    pub fn size(&self) -> usize {
+        match self {
+            Disassembled::Instruction(decoded) => decoded.instruction.size(),
+            Disassembled::Data(_) => 1,
+        }
+    }
+}
+
+/// Percorre uma imagem da memória, decodificando cada instrução junto das palavras de endereço
+/// e valor imediato que a seguem. Palavras inválidas, e instruções cujas palavras seguintes
+/// passam do fim da memória, são retornadas como [`Disassembled::Data`]. Palavras a partir de
+/// [`MEMORY_SIZE`] não têm endereço na máquina e são ignoradas.
+///
+/// ## Exemplo
+///
+/// ```
+/// use isa::*;
+///
+/// let mem = [
+///     0b111000_011_000_000_0, // LOADN R3, #0xff00
+///     0xff00,
+///     0b000010_111_100_000_0, // Inválida
+///     0b001111_000_000_000_0, // HALT
+/// ]
+/// .map(Word);
+///
+/// let items: Vec<_> = Disassembler::new(&mem).collect();
+///
+/// assert_eq!(3, items.len());
+/// assert_eq!(Word(2), items[1].0);
+/// assert_eq!(Disassembled::Data(Word(0b000010_111_100_000_0)), items[1].1);
+/// assert!(matches!(items[2], (Word(3), Disassembled::Instruction(_))));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Disassembler<'a> {
+    mem: &'a [Word],
+    address: usize,
+    mode: DecodeMode,
+}
+
+impl<'a> Disassembler<'a> {
+    /// Cria um *disassembler* que percorre `mem` a partir do endereço 0, no modo
+    /// [`DecodeMode::Lenient`].
+    pub fn new(mem: &'a [Word]) -> Self {
+        Self {
+            mem: &mem[..mem.len().min(MEMORY_SIZE)],
+            address: 0,
+            mode: DecodeMode::default(),
+        }
+    }
+
+    /// Define o modo de decodificação das instruções.
+    pub fn with_mode(mut self, mode: DecodeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Define o endereço a partir do qual a memória é percorrida.
+    pub fn starting_at(mut self, address: usize) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Decodifica a posição `address` de `mem`, sem avançar o *disassembler*.
+    pub fn disassemble_at(&self, address: usize) -> Option<Disassembled> {
+        let v = *self.mem.get(address)?;
+
+        let item = match self.mode.decode_at(self.mem, address) {
+            Ok(decoded) if address + decoded.instruction.size() <= self.mem.len() => {
+                Disassembled::Instruction(decoded)
+            }
+            _ => Disassembled::Data(v),
+        };
+
+        Some(item)
+    }
+}
+
+impl Iterator for Disassembler<'_> {
+    type Item = (Word, Disassembled);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.disassemble_at(self.address)?;
+        let address = Word(u16::try_from(self.address).ok()?);
+
+        self.address += item.size();
+        Some((address, item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Instruction;
+
+    #[test]
+    fn test_truncated_instruction() {
+        // STOREN sem o valor imediato.
+        let mem = [0b111001_000_000_000_0, 0x00ff].map(Word);
+        let items: Vec<_> = Disassembler::new(&mem).collect();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0], (Word(0), Disassembled::Data(mem[0])));
+        assert!(
+            matches!(items[1], (Word(1), Disassembled::Instruction(d)) if d.instruction == Instruction::NOP)
+        );
+    }
+
+    #[test]
+    fn test_modes() {
+        let mem = [0b000100_101_000_000_0, 0b000110_000_100_000_0].map(Word); // RTS; POP FR
+
+        let strict: Vec<_> = Disassembler::new(&mem)
+            .with_mode(DecodeMode::Strict)
+            .map(|(_, item)| item)
+            .collect();
+        assert_eq!(strict[0], Disassembled::Data(mem[0]));
+        assert!(
+            matches!(strict[1], Disassembled::Instruction(d) if d.instruction == Instruction::POP)
+        );
+
+        let lenient = Disassembler::new(&mem).starting_at(1).count();
+        assert_eq!(lenient, 1);
+    }
+
+    #[test]
+    fn test_walks_every_word() {
+        let mem: Vec<Word> = (0..=u16::MAX).step_by(13).map(Word).collect();
+        let total: usize = Disassembler::new(&mem).map(|(_, item)| item.size()).sum();
+
+        assert_eq!(total, mem.len());
+    }
+
+    #[test]
+    fn test_stops_at_end_of_memory() {
+        let mut mem = vec![Word(0); MEMORY_SIZE + 0x8001]; // NOPs
+        mem[MEMORY_SIZE - 1] = Word(0b111000_000_000_000_0); // LOADN R0, #...
+
+        let items: Vec<_> = Disassembler::new(&mem).collect();
+
+        assert_eq!(items.len(), MEMORY_SIZE);
+        assert_eq!(
+            items.last(),
+            Some(&(Word(0x7fff), Disassembled::Data(mem[MEMORY_SIZE - 1])))
+        );
+        assert_eq!(Disassembler::new(&mem).disassemble_at(MEMORY_SIZE), None);
+    }
+}
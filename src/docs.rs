@@ -0,0 +1,198 @@
+use crate::locale::documentation;
+use crate::{Instruction, Locale};
+
+impl Instruction {
+    /// Retorna a documentação completa da instrução, em *markdown*, sem a seção da máscara.
+    pub fn documentation(&self) -> String {
+        self.lines()
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
+
+    /// Retorna o texto que precede a primeira seção da documentação da instrução.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let description = Instruction::ADDC.description().unwrap();
+    /// assert!(description.starts_with("Realiza a soma dos valores"));
+    /// assert_eq!(None, Instruction::INPUT.description());
+    /// ```
+    pub fn description(&self) -> Option<String> {
+        let lines = self.lines().take_while(|line| heading(line).is_none());
+        non_empty(lines.collect())
+    }
+
+    /// Retorna a seção `# Operação` da documentação da instrução.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// assert_eq!(Some("`Rx` ← MEM(`END`)".to_string()), Instruction::LOAD.operation());
+    /// ```
+    pub fn operation(&self) -> Option<String> {
+        self.section("Operação")
+    }
+
+    /// Retorna o código da seção `# Uso` da documentação da instrução, sem os delimitadores do
+    /// bloco.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// assert_eq!(Some("LOAD Rx, END".to_string()), Instruction::LOAD.usage());
+    /// ```
+    pub fn usage(&self) -> Option<String> {
+        self.section("Uso").and_then(|section| code(&section))
+    }
+
+    /// Retorna o código da seção `# Exemplo` da documentação da instrução, sem os delimitadores
+    /// do bloco.
+    pub fn example(&self) -> Option<String> {
+        self.section("Exemplo").and_then(|section| code(&section))
+    }
+
+    /// Retorna a descrição da instrução no idioma `locale`. Em [`Locale::Portuguese`], é a
+    /// mesma de [`Instruction::description`].
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// assert_eq!(
+    ///     Some("Increments register `Rx` by one.".to_string()),
+    ///     Instruction::INC.localized_description(Locale::English)
+    /// );
+    /// ```
+    pub fn localized_description(&self, locale: Locale) -> Option<String> {
+        if locale == Locale::Portuguese {
+            return self.description();
+        }
+
+        if let Some(condition) = self.condition() {
+            let action = if self.is_call() { "call" } else { "jump" };
+            let action = documentation(&format!("description.{action}"))?;
+            let condition = documentation(&format!("condition.{condition:?}"))?;
+
+            return Some(format!("{action}{condition}."));
+        }
+
+        documentation(&format!("description.{self}")).map(str::to_string)
+    }
+
+    /// Retorna a seção `# Operação` no idioma `locale`. As operações escritas apenas com
+    /// símbolos são as mesmas em todos os idiomas.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// assert_eq!(Some("None".to_string()), Instruction::NOP.localized_operation(Locale::English));
+    /// assert_eq!(
+    ///     Instruction::LOAD.operation(),
+    ///     Instruction::LOAD.localized_operation(Locale::English)
+    /// );
+    /// ```
+    pub fn localized_operation(&self, locale: Locale) -> Option<String> {
+        let operation = self.operation()?;
+
+        match locale {
+            Locale::Portuguese => Some(operation),
+            Locale::English => Some(
+                documentation(&format!("operation.{self}"))
+                    .map(str::to_string)
+                    .unwrap_or(operation),
+            ),
+        }
+    }
+
+    /// Retorna o código da seção `# Uso` no idioma `locale`. O código é o mesmo em todos os
+    /// idiomas; veja [`Instruction::usage`].
+    pub fn localized_usage(&self, _locale: Locale) -> Option<String> {
+        self.usage()
+    }
+
+    /// Retorna o código da seção `# Exemplo` no idioma `locale`. O código é o mesmo em todos os
+    /// idiomas; veja [`Instruction::example`].
+    pub fn localized_example(&self, _locale: Locale) -> Option<String> {
+        self.example()
+    }
+
+    /// Retorna o conteúdo da seção de primeiro nível `# title` da documentação da instrução.
+    /// Subseções (`## ...`) fazem parte da seção que as contém.
+    pub fn section(&self, title: &str) -> Option<String> {
+        let lines = self
+            .lines()
+            .skip_while(|line| heading(line) != Some(title))
+            .skip(1)
+            .take_while(|line| heading(line).is_none());
+
+        non_empty(lines.collect())
+    }
+
+    fn lines(&self) -> impl Iterator<Item = &'static str> {
+        self.doc_lines()
+            .iter()
+            .map(|line| line.strip_prefix(' ').unwrap_or(line))
+    }
+}
+
+/// Retorna o título da linha, caso seja um cabeçalho de primeiro nível.
+fn heading(line: &str) -> Option<&str> {
+    line.strip_prefix("# ").map(str::trim)
+}
+
+/// Remove os delimitadores (` ``` `) dos blocos de código de uma seção.
+fn code(section: &str) -> Option<String> {
+    let lines = section.lines().filter(|line| !line.starts_with("```"));
+    non_empty(lines.collect())
+}
+
+fn non_empty(lines: Vec<&str>) -> Option<String> {
+    let text = lines.join("\n").trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections() {
+        assert_eq!(
+            Instruction::MOV.usage().unwrap(),
+            "MOV Rx, Ry\nMOV Rx, SP\nMOV SP, Rx"
+        );
+        assert_eq!(Instruction::OUTCHAR.example().unwrap(), "OUTCHAR R1, R0");
+        assert!(Instruction::OUTCHAR
+            .section("Cores")
+            .unwrap()
+            .contains("## Exemplo"));
+        assert!(!Instruction::OUTCHAR
+            .description()
+            .unwrap()
+            .contains("Cores"));
+        assert_eq!(Instruction::SOUND.usage(), None);
+        assert!(!Instruction::ADD.documentation().contains("Máscara"));
+    }
+
+    #[test]
+    fn test_every_documented_instruction_has_usage() {
+        for instruction in Instruction::iter() {
+            if instruction.description().is_some() {
+                assert!(instruction.usage().is_some(), "{instruction}");
+                assert!(instruction.operation().is_some(), "{instruction}");
+            }
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use thiserror::Error;
+
+use crate::locale::{key, message};
+use crate::{
+    Bits, DecodedInstruction, Instruction, Locale, Localize, Mov, OperandFormat, Operands,
+    Register, StackOperand, Word,
+};
+
+/// Maior quantidade de *bits* que cabe no campo `N` das instruções de deslocamento.
+const MAX_SHIFT: usize = 0b1111;
+
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub enum EncodeError {
+    InvalidOperands { instruction: Instruction },
+
+    RegisterOutOfRange { register: Register },
+
+    ShiftOutOfRange { n: usize },
+
+    MissingAddress { instruction: Instruction },
+
+    MissingImmediate { instruction: Instruction },
+
+    UnexpectedAddress { instruction: Instruction },
+
+    UnexpectedImmediate { instruction: Instruction },
+}
+
+impl Localize for EncodeError {
+    fn localize(&self, locale: Locale) -> String {
+        match self {
+            EncodeError::InvalidOperands { instruction } => message(
+                locale,
+                key::ENCODE_INVALID_OPERANDS,
+                &[("instruction", instruction)],
+            ),
+            EncodeError::RegisterOutOfRange { register } => message(
+                locale,
+                key::ENCODE_REGISTER_OUT_OF_RANGE,
+                &[("register", register)],
+            ),
+            EncodeError::ShiftOutOfRange { n } => {
+                message(locale, key::ENCODE_SHIFT_OUT_OF_RANGE, &[("n", n)])
+            }
+            EncodeError::MissingAddress { instruction } => message(
+                locale,
+                key::ENCODE_MISSING_ADDRESS,
+                &[("instruction", instruction)],
+            ),
+            EncodeError::MissingImmediate { instruction } => message(
+                locale,
+                key::ENCODE_MISSING_IMMEDIATE,
+                &[("instruction", instruction)],
+            ),
+            EncodeError::UnexpectedAddress { instruction } => message(
+                locale,
+                key::ENCODE_UNEXPECTED_ADDRESS,
+                &[("instruction", instruction)],
+            ),
+            EncodeError::UnexpectedImmediate { instruction } => message(
+                locale,
+                key::ENCODE_UNEXPECTED_IMMEDIATE,
+                &[("instruction", instruction)],
+            ),
+        }
+    }
+}
+
+fn register(register: Register) -> Result<u16, EncodeError> {
+    register
+        .index()
+        .map(|index| index as u16)
+        .ok_or(EncodeError::RegisterOutOfRange { register })
+}
+
+impl DecodedInstruction {
+    /// Codifica apenas a palavra da instrução, sem as palavras de endereço ou valor imediato.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let add = DecodedInstruction::new(Instruction::ADD, Operands::RxRyRz { rx: Register::R3, ry: Register::R0, rz: Register::R7 });
+    /// assert_eq!(Word(0b100000_011_000_111_0), add.encode_word().unwrap());
+    /// ```
+    pub fn encode_word(&self) -> Result<Word, EncodeError> {
+        let invalid = || EncodeError::InvalidOperands {
+            instruction: self.instruction,
+        };
+
+        let v = self.instruction.mask().0;
+
+        let v = match (self.instruction.format(), self.operands) {
+            (
+                OperandFormat::None | OperandFormat::AddressImmediate | OperandFormat::Address,
+                Operands::None,
+            ) => v,
+            (
+                OperandFormat::Rx
+                | OperandFormat::RxAddress
+                | OperandFormat::RxImmediate
+                | OperandFormat::AddressRx,
+                Operands::Rx { rx },
+            ) => v.set_bits(register(rx)?, 7..=9),
+            (OperandFormat::RxRy, Operands::RxRy { rx, ry }) => {
+                let v = v.set_bits(register(rx)?, 7..=9);
+                v.set_bits(register(ry)?, 4..=6)
+            }
+            (OperandFormat::RxRyRz, Operands::RxRyRz { rx, ry, rz }) => {
+                let v = v.set_bits(register(rx)?, 7..=9);
+                let v = v.set_bits(register(ry)?, 4..=6);
+                v.set_bits(register(rz)?, 1..=3)
+            }
+            (OperandFormat::Shift, Operands::Shift { rx, n }) => {
+                if n > MAX_SHIFT {
+                    return Err(EncodeError::ShiftOutOfRange { n });
+                }
+
+                let v = v.set_bits(register(rx)?, 7..=9);
+                v.set_bits(n as u16, 0..=3)
+            }
+            (OperandFormat::Mov, Operands::Mov(mov)) => match mov {
+                Mov::Register { rx, ry } => {
+                    let v = v.set_bits(register(rx)?, 7..=9);
+                    v.set_bits(register(ry)?, 4..=6)
+                }
+                Mov::FromSp { rx } => v.set_bits(register(rx)?, 7..=9).set_bits(0b01, 0..=1),
+                Mov::ToSp { rx } => v.set_bits(register(rx)?, 7..=9).set_bits(0b11, 0..=1),
+            },
+            (OperandFormat::Stack, Operands::Stack(operand)) => match operand {
+                StackOperand::Register(rx) => v.set_bits(register(rx)?, 7..=9),
+                StackOperand::FlagRegister => v.set_bits(1, 6..=6),
+            },
+            _ => return Err(invalid()),
+        };
+
+        Ok(Word(v))
+    }
+
+    /// Codifica a instrução, retornando a sua palavra seguida, quando presentes, do endereço
+    /// `END` e do valor imediato `NR`.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let loadn = DecodedInstruction::new(Instruction::LOADN, Operands::Rx { rx: Register::R3 })
+    ///     .with_immediate(Word(0xff00));
+    /// assert_eq!(vec![Word(0b111000_011_000_000_0), Word(0xff00)], loadn.encode().unwrap());
+    /// ```
+    pub fn encode(&self) -> Result<Vec<Word>, EncodeError> {
+        let format = self.instruction.format();
+        let instruction = self.instruction;
+
+        let mut words = vec![self.encode_word()?];
+
+        match (format.has_address(), self.address) {
+            (true, Some(address)) => words.push(address),
+            (true, None) => return Err(EncodeError::MissingAddress { instruction }),
+            (false, Some(_)) => return Err(EncodeError::UnexpectedAddress { instruction }),
+            (false, None) => {}
+        }
+
+        match (format.has_immediate(), self.immediate) {
+            (true, Some(immediate)) => words.push(immediate),
+            (true, None) => return Err(EncodeError::MissingImmediate { instruction }),
+            (false, Some(_)) => return Err(EncodeError::UnexpectedImmediate { instruction }),
+            (false, None) => {}
+        }
+
+        Ok(words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_round_trip() {
+        let codes = [
+            0b100000_011_000_111_1, // ADDC R3, R0, R7
+            0b010000_110_100_001_0, // ROTL R6, 2
+            0b110011_010_000_000_1, // MOV R2, SP
+            0b000101_000_100_000_0, // PUSH FR
+            0b000010_001_100_000_0, // JZ
+        ]
+        .map(Word);
+
+        for code in codes {
+            let decoded = Instruction::decode(code).unwrap();
+            assert_eq!(decoded.encode_word().unwrap(), code);
+        }
+    }
+
+    #[test]
+    fn test_encode_trailing_words() {
+        let storen = DecodedInstruction::new(Instruction::STOREN, Operands::None)
+            .with_address(Word(0x00ff))
+            .with_immediate(Word(0b10100));
+        assert_eq!(
+            storen.encode().unwrap(),
+            vec![Word(0b111001_000_000_000_0), Word(0x00ff), Word(0b10100)]
+        );
+
+        let jmp = DecodedInstruction::new(Instruction::JMP, Operands::None);
+        assert_eq!(
+            jmp.encode(),
+            Err(EncodeError::MissingAddress {
+                instruction: Instruction::JMP
+            })
+        );
+    }
+
+    #[test]
+    fn test_encode_out_of_range() {
+        let inc = DecodedInstruction::new(Instruction::INC, Operands::Rx { rx: Register::SP });
+        assert_eq!(
+            inc.encode_word(),
+            Err(EncodeError::RegisterOutOfRange {
+                register: Register::SP
+            })
+        );
+
+        let shift = DecodedInstruction::new(
+            Instruction::SHIFTL0,
+            Operands::Shift {
+                rx: Register::R1,
+                n: 16,
+            },
+        );
+        assert_eq!(
+            shift.encode_word(),
+            Err(EncodeError::ShiftOutOfRange { n: 16 })
+        );
+
+        let load = DecodedInstruction::new(Instruction::LOAD, Operands::Rx { rx: Register::R1 })
+            .with_address(Word(0x00ff))
+            .with_immediate(Word(1));
+        assert_eq!(
+            load.encode(),
+            Err(EncodeError::UnexpectedImmediate {
+                instruction: Instruction::LOAD
+            })
+        );
+
+        let add = DecodedInstruction::new(Instruction::ADD, Operands::Rx { rx: Register::R1 });
+        assert_eq!(
+            add.encode_word(),
+            Err(EncodeError::InvalidOperands {
+                instruction: Instruction::ADD
+            })
+        );
+    }
+}
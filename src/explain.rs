@@ -0,0 +1,402 @@
+use crate::locale::{key, message};
+use crate::{Bits, Instruction, Locale, Localize, OperandFormat, Word};
+
+/// Um campo de *bits* de uma palavra, do *bit* `high` ao *bit* `low`, inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// Nome do campo, que não depende do idioma, como `opcode`, `Rx` ou `mode`. O nome exibido
+    /// é dado por [`Field::label`].
+    pub name: &'static str,
+    pub high: usize,
+    pub low: usize,
+    pub value: u16,
+    /// Significado do valor do campo para a instrução decodificada.
+    pub meaning: Meaning,
+}
+
+/// Significado do valor de um [`Field`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Meaning {
+    /// Texto que não depende do idioma, como um mnemônico, um registrador ou `SP ← Rx`.
+    Text(String),
+    /// O *opcode* não corresponde a nenhuma instrução.
+    Invalid,
+    /// A instrução ignora os *bits* do campo.
+    Ignored,
+}
+
+impl Localize for Meaning {
+    fn localize(&self, locale: Locale) -> String {
+        match self {
+            Meaning::Text(text) => text.clone(),
+            Meaning::Invalid => message(locale, key::EXPLAIN_INVALID, &[]),
+            Meaning::Ignored => message(locale, key::EXPLAIN_IGNORED, &[]),
+        }
+    }
+}
+
+impl Field {
+    fn new(v: Word, name: &'static str, high: usize, low: usize, meaning: Meaning) -> Self {
+        Self {
+            name,
+            high,
+            low,
+            value: v.0.bits(low..=high),
+            meaning,
+        }
+    }
+
+    /// Quantidade de *bits* do campo.
+    pub fn width(&self) -> usize {
+        self.high - self.low + 1
+    }
+
+    /// Retorna o valor do campo em binário, com um dígito por *bit*.
+    pub fn binary(&self) -> String {
+        format!("{:0width$b}", self.value, width = self.width())
+    }
+
+    /// Retorna o nome do campo no idioma `locale`. Só os campos `type`, `mode`, `select` e
+    /// `condition` têm nomes traduzidos; os demais são exibidos como na documentação.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let explanation = Word(0b010000_110_100_001_0).explain(); // ROTL R6, 2
+    ///
+    /// assert_eq!("type", explanation.fields[2].name);
+    /// assert_eq!("tipo", explanation.fields[2].label(Locale::Portuguese));
+    /// assert_eq!("Rx", explanation.fields[1].label(Locale::English));
+    /// ```
+    pub fn label(&self, locale: Locale) -> String {
+        let key = match self.name {
+            "type" => key::EXPLAIN_TYPE,
+            "mode" => key::EXPLAIN_MODE,
+            "select" => key::EXPLAIN_SELECT,
+            "condition" => key::EXPLAIN_CONDITION,
+            name => return name.to_string(),
+        };
+
+        message(locale, key, &[])
+    }
+}
+
+/// Decomposição de uma palavra nos campos de *bits* da sua instrução. Veja [`Word::explain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub word: Word,
+    /// A instrução da palavra, ou [`None`] se a palavra não corresponder a nenhuma instrução.
+    pub instruction: Option<Instruction>,
+    /// Campos da palavra, do mais para o menos significativo.
+    pub fields: Vec<Field>,
+}
+
+impl Word {
+    /// Decompõe a palavra nos campos de *bits* da sua instrução, com o significado de cada um.
+    /// *Bits* que a instrução ignora aparecem em campos chamados `-`. Palavras inválidas são
+    /// decompostas no formato `opcode | Rx | Ry | Rz | c`.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let explanation = Word(0b100000_011_000_111_1).explain(); // ADDC R3, R0, R7
+    ///
+    /// assert_eq!(Some(Instruction::ADDC), explanation.instruction);
+    /// assert_eq!(
+    ///     vec!["opcode", "Rx", "Ry", "Rz", "c"],
+    ///     explanation.fields.iter().map(|f| f.name).collect::<Vec<_>>()
+    /// );
+    /// assert_eq!(
+    ///     " 15  10 9  7 6  4 3  1 0\n\
+    /// ╭──────┬────┬────┬────┬─╮
+    /// │opcode│ Rx │ Ry │ Rz │c│
+    /// ├──────┼────┼────┼────┼─┤
+    /// │100000│011 │000 │111 │1│
+    /// ╰──────┴────┴────┴────┴─╯
+    /// opcode  15..10  100000  ADDC
+    /// Rx      9..7    011     R3
+    /// Ry      6..4    000     R0
+    /// Rz      3..1    111     R7
+    /// c       0       1       ADDC
+    /// ",
+    ///     explanation.to_string()
+    /// );
+    /// ```
+    pub fn explain(&self) -> Explanation {
+        let v = *self;
+
+        let Ok(instruction) = Instruction::get_instruction(v) else {
+            return Explanation {
+                word: v,
+                instruction: None,
+                fields: vec![
+                    Field::new(v, "opcode", 15, 10, Meaning::Invalid),
+                    Field::new(v, "Rx", 9, 7, text(v.rx())),
+                    Field::new(v, "Ry", 6, 4, text(v.ry())),
+                    Field::new(v, "Rz", 3, 1, text(v.rz())),
+                    Field::new(v, "c", 0, 0, text("")),
+                ],
+            };
+        };
+
+        let mnemonic = || text(instruction.mnemonic());
+        let mut fields = vec![Field::new(v, "opcode", 15, 10, mnemonic())];
+        let rx = || Field::new(v, "Rx", 9, 7, text(v.rx()));
+        let ry = || Field::new(v, "Ry", 6, 4, text(v.ry()));
+
+        match instruction.format() {
+            OperandFormat::Rx
+            | OperandFormat::RxAddress
+            | OperandFormat::RxImmediate
+            | OperandFormat::AddressRx => fields.push(rx()),
+            OperandFormat::RxRy => fields.extend([rx(), ry()]),
+            OperandFormat::RxRyRz => {
+                fields.extend([rx(), ry(), Field::new(v, "Rz", 3, 1, text(v.rz()))])
+            }
+            OperandFormat::Shift => fields.extend([
+                rx(),
+                Field::new(v, "type", 6, 4, mnemonic()),
+                Field::new(v, "N", 3, 0, text(v.0.bits(0..=3))),
+            ]),
+            OperandFormat::Mov => {
+                let mode = match v.0.bits(0..=1) {
+                    0b01 => "Rx ← SP",
+                    0b11 => "SP ← Rx",
+                    _ => "Rx ← Ry",
+                };
+                fields.extend([rx(), ry(), Field::new(v, "mode", 1, 0, text(mode))]);
+            }
+            OperandFormat::Stack => {
+                let operand = if v.0.bits(6..=6) == 1 { "FR" } else { "Rx" };
+                fields.extend([rx(), Field::new(v, "FR", 6, 6, text(operand))]);
+            }
+            OperandFormat::Address => fields.push(Field::new(v, "condition", 9, 6, mnemonic())),
+            OperandFormat::None | OperandFormat::AddressImmediate => {}
+        }
+
+        // Os *bits* restantes são agrupados em sequências de *bits* fixos, que selecionam a
+        // instrução, e de *bits* ignorados.
+        let fixed = instruction.fixed_bits().0;
+        let covered = |bit: usize| fields.iter().any(|f| f.low <= bit && bit <= f.high);
+        let mut rest: Vec<Field> = vec![];
+        let mut bit = 10;
+
+        while bit > 0 {
+            bit -= 1;
+
+            if covered(bit) {
+                continue;
+            }
+
+            let is_fixed = fixed.bits(bit..=bit) == 1;
+            let high = bit;
+
+            while bit > 0 && !covered(bit - 1) && (fixed.bits(bit - 1..=bit - 1) == 1) == is_fixed {
+                bit -= 1;
+            }
+
+            rest.push(match (is_fixed, high == 0) {
+                (true, true) => Field::new(v, "c", 0, 0, mnemonic()),
+                (true, false) => Field::new(v, "select", high, bit, mnemonic()),
+                (false, _) => Field::new(v, "-", high, bit, Meaning::Ignored),
+            });
+        }
+
+        fields.extend(rest);
+        fields.sort_by_key(|field| std::cmp::Reverse(field.high));
+
+        Explanation {
+            word: v,
+            instruction: Some(instruction),
+            fields,
+        }
+    }
+}
+
+/// Significado de um campo que não depende do idioma.
+fn text(value: impl ToString) -> Meaning {
+    Meaning::Text(value.to_string())
+}
+
+/// Menor cabeçalho das posições do campo, como `9  7`.
+fn header(field: &Field) -> String {
+    match field.width() {
+        1 => field.high.to_string(),
+        _ => format!("{}  {}", field.high, field.low),
+    }
+}
+
+impl Localize for Explanation {
+    fn localize(&self, locale: Locale) -> String {
+        let labels: Vec<String> = self.fields.iter().map(|f| f.label(locale)).collect();
+        let widths: Vec<usize> = self
+            .fields
+            .iter()
+            .zip(&labels)
+            .map(|(field, label)| {
+                field
+                    .width()
+                    .max(label.chars().count())
+                    .max(header(field).len())
+            })
+            .collect();
+
+        let row = |cells: &[String]| {
+            let cells: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:^width$}"))
+                .collect();
+            format!("│{}│\n", cells.join("│"))
+        };
+
+        let border = |[left, middle, right]: [char; 3]| {
+            let lines: Vec<String> = widths.iter().map(|&width| "─".repeat(width)).collect();
+            format!("{left}{}{right}\n", lines.join(&middle.to_string()))
+        };
+
+        let positions: Vec<String> = self
+            .fields
+            .iter()
+            .zip(&widths)
+            .map(|(field, &width)| match field.width() {
+                1 => format!("{:^width$}", field.high),
+                _ => {
+                    let high = field.high.to_string();
+                    let low = field.low.to_string();
+                    let gap = width - high.len() - low.len();
+                    format!("{high}{}{low}", " ".repeat(gap))
+                }
+            })
+            .collect();
+        let mut text = format!(" {}\n", positions.join(" ").trim_end());
+
+        text += &border(['╭', '┬', '╮']);
+        text += &row(&labels);
+        text += &border(['├', '┼', '┤']);
+        text += &row(&self.fields.iter().map(Field::binary).collect::<Vec<_>>());
+        text += &border(['╰', '┴', '╯']);
+
+        for (field, label) in self.fields.iter().zip(&labels) {
+            let bits = match field.width() {
+                1 => field.high.to_string(),
+                _ => format!("{}..{}", field.high, field.low),
+            };
+
+            let line = format!(
+                "{:<8}{:<8}{:<8}{}",
+                label,
+                bits,
+                field.binary(),
+                field.meaning.localize(locale)
+            );
+            text += &format!("{}\n", line.trim_end());
+        }
+
+        text
+    }
+}
+
+impl std::fmt::Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.localize(Locale::Portuguese))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(v: u16) -> Vec<(&'static str, usize, usize)> {
+        Word(v)
+            .explain()
+            .fields
+            .iter()
+            .map(|f| (f.name, f.high, f.low))
+            .collect()
+    }
+
+    #[test]
+    fn test_fields_cover_the_word() {
+        for v in (0..=u16::MAX).step_by(97) {
+            let explanation = Word(v).explain();
+            let mut next = 15;
+
+            for field in &explanation.fields {
+                assert_eq!(field.high, next, "{v:#018b}");
+                next = field.low.wrapping_sub(1);
+            }
+
+            assert_eq!(next, usize::MAX, "{v:#018b}");
+        }
+    }
+
+    #[test]
+    fn test_layouts() {
+        assert_eq!(
+            layout(0b000010_011_100_000_0), // JGR
+            vec![("opcode", 15, 10), ("condition", 9, 6), ("-", 5, 0)]
+        );
+        assert_eq!(
+            layout(0b010000_110_100_001_0), // ROTL R6, 2
+            vec![
+                ("opcode", 15, 10),
+                ("Rx", 9, 7),
+                ("type", 6, 4),
+                ("N", 3, 0)
+            ]
+        );
+        assert_eq!(
+            layout(0b100100_010_100_000_0), // DEC R2
+            vec![
+                ("opcode", 15, 10),
+                ("Rx", 9, 7),
+                ("select", 6, 6),
+                ("-", 5, 0)
+            ]
+        );
+        assert_eq!(
+            layout(0b001000_100_000_000_0), // SETC
+            vec![("opcode", 15, 10), ("select", 9, 9), ("-", 8, 0)]
+        );
+        assert_eq!(
+            layout(0b000100_000_000_000_1), // RTI
+            vec![("opcode", 15, 10), ("-", 9, 1), ("c", 0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_meanings() {
+        let explanation = Word(0b110011_000_000_001_1).explain(); // MOV SP, R0
+        let mode = explanation
+            .fields
+            .iter()
+            .find(|f| f.name == "mode")
+            .unwrap();
+        assert_eq!(mode.meaning, Meaning::Text("SP ← Rx".to_string()));
+
+        let invalid = Word(0b000010_111_100_000_0).explain();
+        assert_eq!(invalid.instruction, None);
+        assert_eq!(invalid.fields[0].meaning, Meaning::Invalid);
+
+        let jump = Word(0b000010_011_100_000_0).explain(); // JGR
+        assert_eq!(jump.fields[1].meaning, Meaning::Text("JGR".to_string()));
+    }
+
+    #[test]
+    fn test_localized_explanation() {
+        let explanation = Word(0b000010_111_100_000_0).explain();
+        assert!(explanation.to_string().contains("inválida"));
+        assert!(explanation.localize(Locale::English).contains("invalid"));
+
+        let explanation = Word(0b110011_000_000_001_1).explain(); // MOV SP, R0
+        let english = explanation.localize(Locale::English);
+        assert!(english.contains("│mode│"), "{english}");
+        assert!(english.contains("ignored"), "{english}");
+        assert!(explanation.to_string().contains("│modo│"));
+    }
+}
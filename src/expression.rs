@@ -0,0 +1,523 @@
+use crate::AssembleErrorKind;
+
+/// Quantidade máxima de parênteses e operadores unários aninhados em uma expressão, como em
+/// `((((...))))` ou `-~-~...`. Limita a recursão da leitura e da avaliação das expressões.
+const MAX_DEPTH: usize = 256;
+
+/// Operador binário de uma expressão, em ordem crescente de precedência. As comparações
+/// resultam em 1, se verdadeiras, ou 0.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum BinaryOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Or,
+    Xor,
+    And,
+    Shl,
+    Shr,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinaryOp {
+    fn precedence(&self) -> u8 {
+        match self {
+            BinaryOp::Eq
+            | BinaryOp::Ne
+            | BinaryOp::Lt
+            | BinaryOp::Le
+            | BinaryOp::Gt
+            | BinaryOp::Ge => 0,
+            BinaryOp::Or => 1,
+            BinaryOp::Xor => 2,
+            BinaryOp::And => 3,
+            BinaryOp::Shl | BinaryOp::Shr => 4,
+            BinaryOp::Add | BinaryOp::Sub => 5,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 6,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::Or => "|",
+            BinaryOp::Xor => "^",
+            BinaryOp::And => "&",
+            BinaryOp::Shl => "<<",
+            BinaryOp::Shr => ">>",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+        }
+    }
+}
+
+/// Expressão avaliada em tempo de montagem, como `SCREEN_W*2+1` ou `'A'+3072`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Expr {
+    Number(i64),
+    /// Um rótulo ou uma constante.
+    Symbol(String),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    /// Operações aplicadas da esquerda para a direita: `a - b + c` é `(a - b) + c`. Uma sequência
+    /// de operadores forma um único nó, para que a árvore não cresça com o seu comprimento.
+    Binary(Box<Expr>, Vec<(BinaryOp, Expr)>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(i64),
+    Symbol(String),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+/// Indica se `s` é um identificador válido para um rótulo ou uma constante.
+pub(crate) fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Converte um número decimal, hexadecimal (`0x`) ou binário (`0b`).
+fn parse_number(text: &str) -> Result<i64, AssembleErrorKind> {
+    let lower = text.to_ascii_lowercase();
+
+    if let Some(hex) = lower.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16)
+    } else if let Some(bin) = lower.strip_prefix("0b") {
+        i64::from_str_radix(bin, 2)
+    } else {
+        lower.parse()
+    }
+    .map_err(|_| AssembleErrorKind::InvalidNumber {
+        text: text.to_string(),
+    })
+}
+
+/// Converte os caracteres de `text`, interpretando os escapes `\n`, `\t`, `\0`, `\\`, `\'` e
+/// `\"`, em códigos de caracteres.
+pub(crate) fn unescape(text: &str) -> Option<Vec<u16>> {
+    let mut codes = vec![];
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        let c = match c {
+            '\\' => match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                '0' => '\0',
+                c @ ('\\' | '\'' | '"') => c,
+                _ => return None,
+            },
+            c => c,
+        };
+
+        codes.push(u16::try_from(u32::from(c)).ok()?);
+    }
+
+    Some(codes)
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, AssembleErrorKind> {
+    let invalid = || AssembleErrorKind::InvalidExpression {
+        text: text.to_string(),
+    };
+
+    let mut tokens = vec![];
+    let mut rest = text.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let len = if c.is_ascii_alphanumeric() || c == '_' {
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            let word = &rest[..len];
+
+            tokens.push(match c.is_ascii_digit() {
+                true => Token::Number(parse_number(word)?),
+                false => Token::Symbol(word.to_string()),
+            });
+            len
+        } else if c == '\'' {
+            // Literal de caractere, como `'A'` ou `'\''`.
+            let end = rest[1..]
+                .char_indices()
+                .scan(false, |escaped, (i, c)| {
+                    let end = !*escaped && c == '\'';
+                    *escaped = !*escaped && c == '\\';
+                    Some((i, end))
+                })
+                .find(|&(_, end)| end)
+                .map(|(i, _)| i + 1)
+                .ok_or_else(invalid)?;
+
+            let literal = &rest[..=end];
+            let code = match unescape(&literal[1..end]).as_deref() {
+                Some(&[code]) => code,
+                _ => {
+                    return Err(AssembleErrorKind::InvalidCharacter {
+                        text: literal.to_string(),
+                    })
+                }
+            };
+
+            tokens.push(Token::Number(i64::from(code)));
+            end + 1
+        } else if c == '(' || c == ')' {
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            1
+        } else {
+            let op = [
+                "<<", ">>", "<=", ">=", "==", "!=", "<", ">", "+", "-", "*", "/", "%", "&", "|",
+                "^", "~",
+            ]
+            .into_iter()
+            .find(|op| rest.starts_with(op))
+            .ok_or_else(invalid)?;
+
+            tokens.push(Token::Op(op));
+            op.len()
+        };
+
+        rest = rest[len..].trim_start();
+    }
+
+    Ok(tokens)
+}
+
+/// Analisador de expressões por precedência de operadores.
+struct Parser<'a> {
+    text: &'a str,
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn invalid(&self) -> AssembleErrorKind {
+        AssembleErrorKind::InvalidExpression {
+            text: self.text.to_string(),
+        }
+    }
+
+    /// Entra em um parêntese ou operador unário.
+    fn enter(&mut self) -> Result<(), AssembleErrorKind> {
+        if self.depth == MAX_DEPTH {
+            return Err(AssembleErrorKind::ExpressionTooDeep {
+                text: self.text.to_string(),
+            });
+        }
+
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn binary_op(&self) -> Option<BinaryOp> {
+        let op = match self.tokens.get(self.position)? {
+            Token::Op("==") => BinaryOp::Eq,
+            Token::Op("!=") => BinaryOp::Ne,
+            Token::Op("<") => BinaryOp::Lt,
+            Token::Op("<=") => BinaryOp::Le,
+            Token::Op(">") => BinaryOp::Gt,
+            Token::Op(">=") => BinaryOp::Ge,
+            Token::Op("|") => BinaryOp::Or,
+            Token::Op("^") => BinaryOp::Xor,
+            Token::Op("&") => BinaryOp::And,
+            Token::Op("<<") => BinaryOp::Shl,
+            Token::Op(">>") => BinaryOp::Shr,
+            Token::Op("+") => BinaryOp::Add,
+            Token::Op("-") => BinaryOp::Sub,
+            Token::Op("*") => BinaryOp::Mul,
+            Token::Op("/") => BinaryOp::Div,
+            Token::Op("%") => BinaryOp::Rem,
+            _ => return None,
+        };
+
+        Some(op)
+    }
+
+    fn primary(&mut self) -> Result<Expr, AssembleErrorKind> {
+        let depth = self.depth;
+
+        let expr = match self.next() {
+            Some(Token::Number(n)) => Expr::Number(n),
+            Some(Token::Symbol(name)) => Expr::Symbol(name),
+            Some(Token::Op("-")) => {
+                self.enter()?;
+                Expr::Neg(Box::new(self.primary()?))
+            }
+            Some(Token::Op("~")) => {
+                self.enter()?;
+                Expr::Not(Box::new(self.primary()?))
+            }
+            Some(Token::Open) => {
+                self.enter()?;
+                let expr = self.expression(0)?;
+
+                if self.next() != Some(Token::Close) {
+                    return Err(self.invalid());
+                }
+
+                expr
+            }
+            _ => return Err(self.invalid()),
+        };
+
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    fn expression(&mut self, min_precedence: u8) -> Result<Expr, AssembleErrorKind> {
+        let lhs = self.primary()?;
+        let mut operations = vec![];
+
+        while let Some(op) = self
+            .binary_op()
+            .filter(|op| op.precedence() >= min_precedence)
+        {
+            self.position += 1;
+            operations.push((op, self.expression(op.precedence() + 1)?));
+        }
+
+        Ok(match operations.is_empty() {
+            true => lhs,
+            false => Expr::Binary(Box::new(lhs), operations),
+        })
+    }
+}
+
+impl Expr {
+    /// Lê a expressão `text`.
+    pub(crate) fn parse(text: &str) -> Result<Expr, AssembleErrorKind> {
+        let mut parser = Parser {
+            text,
+            tokens: tokenize(text)?,
+            position: 0,
+            depth: 0,
+        };
+
+        let expr = parser.expression(0)?;
+
+        if parser.position != parser.tokens.len() {
+            return Err(parser.invalid());
+        }
+
+        Ok(expr)
+    }
+
+    /// Avalia a expressão, obtendo o valor de cada símbolo com `symbol`.
+    pub(crate) fn evaluate<F>(&self, symbol: &mut F) -> Result<i64, AssembleErrorKind>
+    where
+        F: FnMut(&str) -> Result<i64, AssembleErrorKind>,
+    {
+        let overflow = || AssembleErrorKind::InvalidExpression {
+            text: self.to_string(),
+        };
+
+        match self {
+            Expr::Number(n) => Ok(*n),
+            Expr::Symbol(name) => symbol(name),
+            Expr::Neg(expr) => expr.evaluate(symbol)?.checked_neg().ok_or_else(overflow),
+            Expr::Not(expr) => Ok(!expr.evaluate(symbol)?),
+            Expr::Binary(lhs, operations) => {
+                let mut a = lhs.evaluate(symbol)?;
+
+                for (op, rhs) in operations {
+                    let b = rhs.evaluate(symbol)?;
+                    a = op.apply(a, b)?.ok_or_else(overflow)?;
+                }
+
+                Ok(a)
+            }
+        }
+    }
+}
+
+impl BinaryOp {
+    /// Aplica o operador, retornando [`None`] se o resultado não couber em um `i64`.
+    fn apply(&self, a: i64, b: i64) -> Result<Option<i64>, AssembleErrorKind> {
+        let value = match self {
+            BinaryOp::Eq => Some(i64::from(a == b)),
+            BinaryOp::Ne => Some(i64::from(a != b)),
+            BinaryOp::Lt => Some(i64::from(a < b)),
+            BinaryOp::Le => Some(i64::from(a <= b)),
+            BinaryOp::Gt => Some(i64::from(a > b)),
+            BinaryOp::Ge => Some(i64::from(a >= b)),
+            BinaryOp::Or => Some(a | b),
+            BinaryOp::Xor => Some(a ^ b),
+            BinaryOp::And => Some(a & b),
+            BinaryOp::Shl => u32::try_from(b).ok().and_then(|b| a.checked_shl(b)),
+            BinaryOp::Shr => u32::try_from(b).ok().and_then(|b| a.checked_shr(b)),
+            BinaryOp::Add => a.checked_add(b),
+            BinaryOp::Sub => a.checked_sub(b),
+            BinaryOp::Mul => a.checked_mul(b),
+            BinaryOp::Div | BinaryOp::Rem if b == 0 => {
+                return Err(AssembleErrorKind::DivisionByZero)
+            }
+            BinaryOp::Div => a.checked_div(b),
+            BinaryOp::Rem => a.checked_rem(b),
+        };
+
+        Ok(value)
+    }
+}
+
+impl std::fmt::Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let operand = |f: &mut std::fmt::Formatter<'_>, expr: &Expr| match expr {
+            Expr::Binary(..) => write!(f, "({expr})"),
+            expr => write!(f, "{expr}"),
+        };
+
+        match self {
+            Expr::Number(n) => write!(f, "{n}"),
+            Expr::Symbol(name) => write!(f, "{name}"),
+            Expr::Neg(expr) => {
+                write!(f, "-")?;
+                operand(f, expr)
+            }
+            Expr::Not(expr) => {
+                write!(f, "~")?;
+                operand(f, expr)
+            }
+            Expr::Binary(lhs, operations) => {
+                operand(f, lhs)?;
+
+                for (op, rhs) in operations {
+                    write!(f, "{}", op.symbol())?;
+                    operand(f, rhs)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(text: &str) -> Result<i64, AssembleErrorKind> {
+        Expr::parse(text)?.evaluate(&mut |name| match name {
+            "SCREEN_W" => Ok(40),
+            name => Err(AssembleErrorKind::UndefinedLabel {
+                label: name.to_string(),
+            }),
+        })
+    }
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(evaluate("SCREEN_W*2+1"), Ok(81));
+        assert_eq!(evaluate("1 + 2 * 3 - 4"), Ok(3));
+        assert_eq!(evaluate("(1 + 2) * 3"), Ok(9));
+        assert_eq!(evaluate("1 << 4 | 1"), Ok(17));
+        assert_eq!(evaluate("10 - 3 - 2"), Ok(5));
+        assert_eq!(evaluate("-0x10 + ~0"), Ok(-17));
+        assert_eq!(evaluate("'A' + 3072"), Ok(3137));
+        assert_eq!(evaluate("'\\''"), Ok(39));
+        assert_eq!(evaluate("0b101 % 3"), Ok(2));
+        assert_eq!(evaluate("SCREEN_W >= 40"), Ok(1));
+        assert_eq!(evaluate("1 + 1 != 2 | 0"), Ok(0));
+        assert_eq!(evaluate("2 < 1 << 2"), Ok(1));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            evaluate("1 / (2 - 2)"),
+            Err(AssembleErrorKind::DivisionByZero)
+        );
+        assert!(matches!(
+            evaluate("1 +"),
+            Err(AssembleErrorKind::InvalidExpression { .. })
+        ));
+        assert!(matches!(
+            evaluate("(1"),
+            Err(AssembleErrorKind::InvalidExpression { .. })
+        ));
+        assert!(matches!(
+            evaluate("0xfg"),
+            Err(AssembleErrorKind::InvalidNumber { .. })
+        ));
+        assert!(matches!(
+            evaluate("'ab'"),
+            Err(AssembleErrorKind::InvalidCharacter { .. })
+        ));
+        assert!(matches!(
+            evaluate("x"),
+            Err(AssembleErrorKind::UndefinedLabel { .. })
+        ));
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert!(matches!(
+            Expr::parse(&nested),
+            Err(AssembleErrorKind::ExpressionTooDeep { .. })
+        ));
+        assert!(matches!(
+            Expr::parse(&format!("{}1", "-~".repeat(100_000))),
+            Err(AssembleErrorKind::ExpressionTooDeep { .. })
+        ));
+
+        // Sequências de operadores não são aninhamento, por mais longas que sejam.
+        assert_eq!(
+            evaluate(&format!("{}1", "1 + ".repeat(100_000))),
+            Ok(100_001)
+        );
+        assert_eq!(
+            evaluate(&format!("{}1", "1 * 1 + ".repeat(100_000))),
+            Ok(100_001)
+        );
+        assert_eq!(
+            evaluate(&format!(
+                "{}2{}",
+                "(".repeat(MAX_DEPTH),
+                ")".repeat(MAX_DEPTH)
+            )),
+            Ok(2)
+        );
+
+        // Cada nível de aninhamento passa por todas as precedências.
+        let level = "1 == 1 | 1 ^ 1 & 1 << 1 + 1 * -(";
+        let deepest = format!(
+            "{}1{}",
+            level.repeat(MAX_DEPTH / 2),
+            ")".repeat(MAX_DEPTH / 2)
+        );
+        assert!(evaluate(&deepest).is_ok());
+    }
+
+    #[test]
+    fn test_display() {
+        let expr = Expr::parse("(a + 1) * -b").unwrap();
+        assert_eq!(expr.to_string(), "(a+1)*-b");
+    }
+}
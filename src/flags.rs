@@ -0,0 +1,202 @@
+use crate::{Bits, DecodedInstruction, FlagIndex, Instruction, Word, WordOutOfRange};
+
+/// Conteúdo do *flag register* ([`crate::Register::FR`]).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct FlagRegister(pub Word);
+
+impl FlagRegister {
+    /// Cria um *flag register* com todos os *bits* limpos.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indica se o *bit* `flag` está setado.
+    pub fn get(&self, flag: FlagIndex) -> bool {
+        self.0.bits(flag.bit()..=flag.bit()) == Word(1)
+    }
+
+    /// Seta ou limpa o *bit* `flag`.
+    pub fn set(&mut self, flag: FlagIndex, value: bool) {
+        self.0 = self.0.set_bits(Word(value as u16), flag.bit()..=flag.bit());
+    }
+
+    /// Retorna uma cópia do *flag register* com o *bit* `flag` setado ou limpo.
+    pub fn with(mut self, flag: FlagIndex, value: bool) -> Self {
+        self.set(flag, value);
+        self
+    }
+}
+
+impl From<Word> for FlagRegister {
+    fn from(value: Word) -> Self {
+        Self(value)
+    }
+}
+
+impl From<FlagRegister> for Word {
+    fn from(value: FlagRegister) -> Self {
+        value.0
+    }
+}
+
+/// Condição de uma instrução de pulo (`J*`) ou de chamada (`C*`), codificada nos *bits* 6 a 9.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Condition {
+    /// Sempre verdadeira (`JMP`, `CALL`).
+    Always = 0,
+    /// [`FlagIndex::EQUAL`] setado (`JEQ`, `CEQ`).
+    Equal = 1,
+    /// [`FlagIndex::EQUAL`] não setado (`JNE`, `CNE`).
+    NotEqual = 2,
+    /// [`FlagIndex::ZERO`] setado (`JZ`, `CZ`).
+    Zero = 3,
+    /// [`FlagIndex::ZERO`] não setado (`JNZ`, `CNZ`).
+    NotZero = 4,
+    /// [`FlagIndex::CARRY`] setado (`JC`, `CC`).
+    Carry = 5,
+    /// [`FlagIndex::CARRY`] não setado (`JNC`, `CNC`).
+    NotCarry = 6,
+    /// [`FlagIndex::GREATER`] setado (`JGR`, `CGR`).
+    Greater = 7,
+    /// [`FlagIndex::LESSER`] setado (`JLE`, `CLE`).
+    Lesser = 8,
+    /// [`FlagIndex::EQUAL`] ou [`FlagIndex::GREATER`] setados (`JEG`, `CEG`).
+    EqualOrGreater = 9,
+    /// [`FlagIndex::EQUAL`] ou [`FlagIndex::LESSER`] setados (`JEL`, `CEL`).
+    EqualOrLesser = 10,
+    /// [`FlagIndex::ARITHMETIC_OVERFLOW`] setado (`JOV`, `COV`).
+    Overflow = 11,
+    /// [`FlagIndex::ARITHMETIC_OVERFLOW`] não setado (`JNO`, `CNO`).
+    NotOverflow = 12,
+    /// [`FlagIndex::DIV_BY_ZERO`] setado (`JDZ`, `CDZ`).
+    DivByZero = 13,
+    /// [`FlagIndex::NEGATIVE`] setado (`JN`, `CN`).
+    Negative = 14,
+}
+
+impl Condition {
+    const ALL: [Condition; 15] = [
+        Condition::Always,
+        Condition::Equal,
+        Condition::NotEqual,
+        Condition::Zero,
+        Condition::NotZero,
+        Condition::Carry,
+        Condition::NotCarry,
+        Condition::Greater,
+        Condition::Lesser,
+        Condition::EqualOrGreater,
+        Condition::EqualOrLesser,
+        Condition::Overflow,
+        Condition::NotOverflow,
+        Condition::DivByZero,
+        Condition::Negative,
+    ];
+
+    /// Retorna a condição codificada nos *bits* 6 a 9 da palavra `v`.
+    pub fn from_word(v: Word) -> Option<Condition> {
+        Condition::ALL.get(usize::from(v.0.bits(6..=9))).copied()
+    }
+
+    /// Versão de [`Condition::from_word`] que recebe um `usize`.
+    #[deprecated(note = "use `Condition::from_word` com uma `Word`")]
+    pub fn from_word_usize(v: usize) -> Result<Option<Condition>, WordOutOfRange> {
+        Ok(Condition::from_word(Word::try_from(v)?))
+    }
+
+    /// Indica se a condição é satisfeita pelo *flag register* `fr`.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let fr = FlagRegister::new().with(FlagIndex::GREATER, true);
+    ///
+    /// assert!(Instruction::JEG.condition().unwrap().evaluate(&fr));
+    /// assert!(!Instruction::JEL.condition().unwrap().evaluate(&fr));
+    /// ```
+    pub fn evaluate(&self, fr: &FlagRegister) -> bool {
+        match self {
+            Condition::Always => true,
+            Condition::Equal => fr.get(FlagIndex::EQUAL),
+            Condition::NotEqual => !fr.get(FlagIndex::EQUAL),
+            Condition::Zero => fr.get(FlagIndex::ZERO),
+            Condition::NotZero => !fr.get(FlagIndex::ZERO),
+            Condition::Carry => fr.get(FlagIndex::CARRY),
+            Condition::NotCarry => !fr.get(FlagIndex::CARRY),
+            Condition::Greater => fr.get(FlagIndex::GREATER),
+            Condition::Lesser => fr.get(FlagIndex::LESSER),
+            Condition::EqualOrGreater => fr.get(FlagIndex::EQUAL) || fr.get(FlagIndex::GREATER),
+            Condition::EqualOrLesser => fr.get(FlagIndex::EQUAL) || fr.get(FlagIndex::LESSER),
+            Condition::Overflow => fr.get(FlagIndex::ARITHMETIC_OVERFLOW),
+            Condition::NotOverflow => !fr.get(FlagIndex::ARITHMETIC_OVERFLOW),
+            Condition::DivByZero => fr.get(FlagIndex::DIV_BY_ZERO),
+            Condition::Negative => fr.get(FlagIndex::NEGATIVE),
+        }
+    }
+}
+
+impl Instruction {
+    /// Retorna a condição da instrução, caso seja um pulo (`J*`) ou uma chamada (`C*`).
+    pub fn condition(&self) -> Option<Condition> {
+        let jump = Instruction::JMP.opcode();
+        let call = Instruction::CALL.opcode();
+
+        if self.opcode() != jump && self.opcode() != call {
+            return None;
+        }
+
+        Condition::from_word(self.mask())
+    }
+}
+
+impl DecodedInstruction {
+    /// Retorna a condição da instrução, caso seja um pulo (`J*`) ou uma chamada (`C*`).
+    pub fn condition(&self) -> Option<Condition> {
+        self.instruction.condition()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_register() {
+        let mut fr = FlagRegister::new();
+        fr.set(FlagIndex::CARRY, true);
+        fr.set(FlagIndex::NEGATIVE, true);
+        assert_eq!(fr.0, Word(0b10_0001_0000));
+
+        fr.set(FlagIndex::CARRY, false);
+        assert!(!fr.get(FlagIndex::CARRY));
+        assert!(fr.get(FlagIndex::NEGATIVE));
+    }
+
+    #[test]
+    fn test_condition_of_instructions() {
+        assert_eq!(Instruction::JMP.condition(), Some(Condition::Always));
+        assert_eq!(Instruction::JN.condition(), Some(Condition::Negative));
+        assert_eq!(Instruction::CEL.condition(), Some(Condition::EqualOrLesser));
+        assert_eq!(Instruction::ADD.condition(), None);
+        assert_eq!(Instruction::RTS.condition(), None);
+    }
+
+    #[test]
+    fn test_compound_conditions() {
+        let equal = FlagRegister::new().with(FlagIndex::EQUAL, true);
+        let lesser = FlagRegister::new().with(FlagIndex::LESSER, true);
+        let none = FlagRegister::new();
+
+        for fr in [equal, lesser] {
+            assert!(Condition::EqualOrLesser.evaluate(&fr));
+        }
+
+        assert!(Condition::EqualOrGreater.evaluate(&equal));
+        assert!(!Condition::EqualOrGreater.evaluate(&lesser));
+        assert!(!Condition::EqualOrLesser.evaluate(&none));
+        assert!(Condition::NotEqual.evaluate(&none));
+        assert!(Condition::Always.evaluate(&none));
+    }
+}
@@ -1,12 +1,54 @@
 //! TODO
-
-use std::mem::size_of;
+#![allow(clippy::unusual_byte_groupings)]
 
 use thiserror::Error;
 
+use crate::locale::{join, key, message};
+
+mod assembler;
+mod bits;
+mod category;
+mod decode;
+mod diagnostic;
+mod disassemble;
+mod docs;
+mod encode;
+mod explain;
+mod expression;
+mod flags;
+mod listing;
+mod locale;
+mod mnemonic;
+mod preprocess;
+mod register;
+mod syntax;
+mod word;
+
+pub use assembler::*;
+pub use bits::*;
+pub use category::*;
+pub use decode::*;
+pub use diagnostic::*;
+pub use disassemble::*;
+pub use encode::*;
+pub use explain::*;
+pub use flags::*;
+pub use listing::*;
+pub use locale::*;
+pub use mnemonic::*;
+pub use preprocess::*;
+pub use register::*;
+pub use syntax::*;
+pub use word::*;
+
 /// Retorna os bits presentes no valor `v` que estão no intervalo `r`.
 /// A contagem começa do *low bit* para o *high bit*.
 ///
+/// Para outros tipos inteiros ou para uma versão que não entra em pânico, veja [`Bits`].
+///
+/// # Panics
+/// Se o início do intervalo for maior que o fim ou se o intervalo exceder a largura de `usize`.
+///
 /// ## Exemplo:
 ///
 /// ```
@@ -17,25 +59,17 @@ use thiserror::Error;
 /// assert_eq!(0b101, bits(v, 3..=5));
 /// ```
 pub fn bits<R: std::ops::RangeBounds<usize>>(v: usize, r: R) -> usize {
-    let start = match r.start_bound() {
-        std::ops::Bound::Included(&n) => n,
-        std::ops::Bound::Unbounded => 0,
-        std::ops::Bound::Excluded(_) => unreachable!(),
-    };
-
-    let end = match r.end_bound() {
-        std::ops::Bound::Included(&n) => n,
-        std::ops::Bound::Excluded(&n) => n - 1,
-        std::ops::Bound::Unbounded => size_of::<usize>() * 8,
-    };
-
-    let mask = (1 << (end - start + 1)) - 1;
-    let ret = v;
-    (ret >> start) & mask
+    Bits::bits(v, r)
 }
 
 /// Altera os bits presentes no valor `v` que estão no intervalo `r` pelos bits `b`.
-/// A contagem começa do *low bit* para o *high bit*.
+/// A contagem começa do *low bit* para o *high bit*. Os bits de `b` que não cabem no intervalo
+/// são descartados.
+///
+/// Para outros tipos inteiros ou para uma versão que não entra em pânico, veja [`Bits`].
+///
+/// # Panics
+/// Se o início do intervalo for maior que o fim ou se o intervalo exceder a largura de `usize`.
 ///
 /// ## Exemplo:
 ///
@@ -47,40 +81,172 @@ pub fn bits<R: std::ops::RangeBounds<usize>>(v: usize, r: R) -> usize {
 /// assert_eq!(0b101110, set_bits(v, 0b11, 1..=2));
 /// ```
 pub fn set_bits<R: std::ops::RangeBounds<usize>>(v: usize, b: usize, r: R) -> usize {
-    let start = match r.start_bound() {
-        std::ops::Bound::Included(&n) => n,
-        std::ops::Bound::Unbounded => 0,
-        std::ops::Bound::Excluded(_) => unreachable!(),
-    };
+    Bits::set_bits(v, b, r)
+}
 
-    let end = match r.end_bound() {
-        std::ops::Bound::Included(&n) => n,
-        std::ops::Bound::Excluded(&n) => n - 1,
-        std::ops::Bound::Unbounded => size_of::<usize>() * 8,
-    };
+/// Erro retornado quando uma palavra não corresponde a nenhuma [`Instruction`].
+///
+/// A mensagem mostra a palavra em hexadecimal e em binário, separada nos campos
+/// `opcode_Rx_Ry_Rz_c`, o endereço em que ela foi encontrada (quando conhecido) e as instruções
+/// mais próximas.
+///
+/// ## Exemplo
+///
+/// ```
+/// use isa::*;
+///
+/// let mem = [Word(0b000000_000_000_000_0), Word(0b000010_111_100_000_0)];
+/// let Err(DecodeAtError::InvalidInstruction(err)) = Instruction::decode_at(&mem, 1) else {
+///     unreachable!()
+/// };
+///
+/// assert_eq!(Some(Word(1)), err.address());
+/// assert_eq!(
+///     vec![Instruction::JGR, Instruction::JOV, Instruction::JDZ, Instruction::JN],
+///     err.nearest()
+/// );
+/// assert_eq!(
+///     "Instrução inválida: 0x0bc0 (000010_111_100_000_0) no endereço 0x0001; \
+///      mais próximas: JGR, JOV, JDZ, JN",
+///     err.to_string()
+/// );
+/// ```
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub struct InvalidInstruction {
+    code: Word,
+    address: Option<Word>,
+}
 
-    let len = end - start + 1;
-    let zeros = !(((1 << len) - 1) << start);
+impl InvalidInstruction {
+    pub(crate) fn new(code: Word) -> Self {
+        Self {
+            code,
+            address: None,
+        }
+    }
 
-    let val = v & zeros;
+    /// Associa o erro ao endereço `address` da memória.
+    pub fn at(mut self, address: Word) -> Self {
+        self.address = Some(address);
+        self
+    }
 
-    (b << start) | val
+    /// Retorna a palavra que não corresponde a nenhuma instrução.
+    pub fn code(&self) -> Word {
+        self.code
+    }
+
+    /// Retorna o endereço em que a palavra foi encontrada, quando conhecido.
+    pub fn address(&self) -> Option<Word> {
+        self.address
+    }
+
+    /// Retorna as instruções de mesmo OPCODE cujos *bits* fixos diferem da palavra na menor
+    /// quantidade de posições.
+    pub fn nearest(&self) -> Vec<Instruction> {
+        let distance =
+            |i: &Instruction| ((self.code.0 ^ i.mask().0) & i.fixed_bits().0).count_ones();
+
+        let candidates: Vec<Instruction> = Instruction::with_opcode(self.code.opcode()).collect();
+
+        let Some(min) = candidates.iter().map(distance).min() else {
+            return vec![];
+        };
+
+        candidates
+            .into_iter()
+            .filter(|i| distance(i) == min)
+            .collect()
+    }
 }
 
-#[derive(Error, Debug, PartialEq)]
-#[error("Instrução inválida: {code}")]
-pub struct InvalidInstruction {
-    code: usize,
+/// Formata `v` em binário, separando os campos `opcode_Rx_Ry_Rz_c`.
+fn format_fields(v: Word) -> String {
+    format!(
+        "{:06b}_{:03b}_{:03b}_{:03b}_{:b}",
+        v.0.bits(10..=15),
+        v.0.bits(7..=9),
+        v.0.bits(4..=6),
+        v.0.bits(1..=3),
+        v.0.bits(0..=0)
+    )
+}
+
+impl Localize for InvalidInstruction {
+    fn localize(&self, locale: Locale) -> String {
+        let code = format!("{:#06x}", self.code);
+        let fields = format_fields(self.code);
+        let mut text = message(
+            locale,
+            key::INVALID_INSTRUCTION,
+            &[("code", &code), ("fields", &fields)],
+        );
+
+        if let Some(address) = self.address {
+            let address = format!("{address:#06x}");
+            text += &message(
+                locale,
+                key::INVALID_INSTRUCTION_ADDRESS,
+                &[("address", &address)],
+            );
+        }
+
+        let nearest = self.nearest();
+        if !nearest.is_empty() {
+            let nearest = join(&nearest);
+            text += &message(
+                locale,
+                key::INVALID_INSTRUCTION_NEAREST,
+                &[("nearest", &nearest)],
+            );
+        }
+
+        text
+    }
+}
+
+/// Indica se existe alguma palavra que satisfaz ao mesmo tempo as máscaras das duas instruções.
+const fn masks_overlap(code_a: usize, mask_a: usize, code_b: usize, mask_b: usize) -> bool {
+    (code_a ^ code_b) & mask_a & mask_b == 0
+}
+
+/// Verifica, em tempo de compilação, que cada par de instruções é disjunto sob suas máscaras.
+macro_rules! check_disjoint {
+    ($name:ident $code:literal $mask:literal $(, $rest_name:ident $rest_code:literal $rest_mask:literal)*) => {
+        $(assert!(
+            !masks_overlap($code, $mask, $rest_code, $rest_mask),
+            concat!(
+                "As instruções ",
+                stringify!($name),
+                " e ",
+                stringify!($rest_name),
+                " podem ser decodificadas a partir da mesma palavra"
+            )
+        );)*
+
+        check_disjoint!($($rest_name $rest_code $rest_mask),*);
+    };
+    () => {};
 }
 
 macro_rules! instruction_set {
-    ($($(#[$doc:meta])* $name:ident $code:literal $mask:literal),+) => {
+    ($($(#[doc = $doc:literal])* $name:ident $code:literal $mask:literal $format:ident $category:ident),+) => {
+
+        const _: () = {
+            $(assert!(
+                $code & !$mask == 0,
+                concat!("O código da instrução ", stringify!($name), " não satisfaz a sua máscara")
+            );)+
+
+            check_disjoint!($($name $code $mask),+);
+        };
 
         /// Conjunto de instruções presentes na Arquitetura do Processador ICMC.
-        #[derive(Debug, Copy, Clone, PartialEq)]
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
         pub enum Instruction {
             $(
-                $(#[$doc])*
+                $(#[doc = $doc])*
                 #[doc = "# Máscara\n"]
                 #[doc = "```txt"]
                 #[doc = stringify!($mask)]
@@ -91,49 +257,160 @@ macro_rules! instruction_set {
 
         impl std::fmt::Display for Instruction {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-                match self {
-                    $(Instruction::$name => write!(f, "{}", stringify!($name))),+,
-                }
+                write!(f, "{}", self.mnemonic())
             }
         }
 
         impl Instruction {
 
+            /// Quantidade de instruções do conjunto.
+            pub const COUNT: usize = [$(stringify!($name)),+].len();
+
+            /// Todas as instruções do conjunto, na ordem em que foram declaradas.
+            pub const ALL: [Instruction; Instruction::COUNT] = [$(Instruction::$name),+];
+
+            /// Retorna o mnemônico da instrução.
+            pub fn mnemonic(&self) -> &'static str {
+                match self {
+                    $(Instruction::$name => stringify!($name)),+,
+                }
+            }
+
             /// Retorna o OPCODE da instrução.
-            pub fn opcode(&self) -> usize {
-                let code = match self {
-                    $(Instruction::$name => $code),+,
-                };
-                bits(code, 10..=15)
+            pub fn opcode(&self) -> u16 {
+                self.mask().opcode()
+            }
+
+            /// Retorna um iterador sobre todas as instruções do conjunto.
+            ///
+            /// ## Exemplo
+            ///
+            /// ```
+            /// use isa::*;
+            ///
+            /// assert_eq!(Instruction::COUNT, Instruction::iter().count());
+            /// assert_eq!(Some(Instruction::LOAD), Instruction::iter().next());
+            /// ```
+            pub fn iter() -> impl Iterator<Item = Instruction> {
+                Instruction::ALL.into_iter()
+            }
+
+            /// Retorna todas as instruções que compartilham o OPCODE `opcode`, na ordem em que
+            /// foram declaradas.
+            ///
+            /// ## Exemplo
+            ///
+            /// ```
+            /// use isa::*;
+            ///
+            /// let jumps: Vec<Instruction> = Instruction::with_opcode(0b000010).collect();
+            /// assert_eq!(15, jumps.len());
+            /// assert_eq!(Instruction::JMP, jumps[0]);
+            /// ```
+            pub fn with_opcode(opcode: u16) -> impl Iterator<Item = Instruction> {
+                Instruction::iter().filter(move |i| i.opcode() == opcode)
             }
 
             /// Retorna a máscara da instrução.
-            pub fn mask(&self) -> usize {
+            pub fn mask(&self) -> Word {
+                match self {
+                    $(Instruction::$name => Word($code)),+,
+                }
+            }
+
+            /// Retorna os *bits* da palavra que são fixos para esta instrução, isto é, que são
+            /// comparados com [`Instruction::mask`] durante a decodificação.
+            pub fn fixed_bits(&self) -> Word {
+                match self {
+                    $(Instruction::$name => Word($mask)),+,
+                }
+            }
+
+            /// Retorna as linhas da documentação da instrução, sem a seção da máscara.
+            pub(crate) fn doc_lines(&self) -> &'static [&'static str] {
                 match self {
-                    $(Instruction::$name => $code),+,
+                    $(Instruction::$name => &[$($doc),*]),+,
+                }
+            }
+
+            /// Retorna o formato dos operandos da instrução.
+            pub fn format(&self) -> OperandFormat {
+                match self {
+                    $(Instruction::$name => OperandFormat::$format),+,
+                }
+            }
+
+            /// Retorna a categoria da instrução.
+            pub fn category(&self) -> Category {
+                match self {
+                    $(Instruction::$name => Category::$category),+,
                 }
             }
 
            /// Retorna qual [`Instruction`] está presente no argumento `v`.
-           /// Se a instrução for inválida, irá retornar [`Instruction::InvalidInstruction`].
+           /// Se a instrução for inválida, irá retornar [`InvalidInstruction`].
            ///
            /// ## Exemplo
            ///
            /// ```
            /// use isa::*;
            ///
-           /// let mem = 0b1100001000100011; // LOAD
+           /// let mem = Word(0b1100001000100011); // LOAD
            /// assert_eq!(Instruction::LOAD, Instruction::get_instruction(mem).unwrap());
            /// ```
-            pub fn get_instruction(v: usize) -> Result<Instruction, InvalidInstruction> {
-                $(if (v & $mask) == $code {
+            pub fn get_instruction(v: Word) -> Result<Instruction, InvalidInstruction> {
+                DECODE_TABLE[usize::from(v)].ok_or(InvalidInstruction::new(v))
+            }
+
+            /// Versão de [`Instruction::get_instruction`] que recebe um `usize`. Valores maiores
+            /// que uma palavra retornam [`UsizeDecodeError::WordOutOfRange`].
+            #[deprecated(note = "use `Instruction::get_instruction` com uma `Word`")]
+            pub fn get_instruction_usize(v: usize) -> Result<Instruction, UsizeDecodeError> {
+                Ok(Instruction::get_instruction(Word::try_from(v)?)?)
+            }
+
+            /// Decodifica `v` testando as máscaras na ordem em que as instruções foram declaradas.
+            #[cfg(test)]
+            fn get_instruction_linear(v: Word) -> Result<Instruction, InvalidInstruction> {
+                $(if (v.0 & $mask) == $code {
                     return Ok(Instruction::$name);
                 })+
 
-                Err(InvalidInstruction { code: v })
+                Err(InvalidInstruction::new(v))
             }
 
         }
+
+        /// Tabela indexada pela palavra, contendo a [`Instruction`] decodificada de cada uma das
+        /// 2¹⁶ palavras possíveis.
+        ///
+        /// É preenchida em tempo de compilação percorrendo as instruções da última para a
+        /// primeira, de modo que, assim como na busca linear, a instrução declarada primeiro
+        /// tenha prioridade.
+        static DECODE_TABLE: [Option<Instruction>; 1 << 16] = {
+            const ENTRIES: &[(usize, usize, Instruction)] = &[$(($code, $mask, Instruction::$name)),+];
+
+            let mut table = [None; 1 << 16];
+            let mut i = ENTRIES.len();
+
+            while i > 0 {
+                i -= 1;
+                let (code, mask, instruction) = ENTRIES[i];
+
+                // Percorre todas as combinações dos bits livres da máscara.
+                let free = !mask & 0xffff;
+                let mut sub = 0;
+                loop {
+                    table[code | sub] = Some(instruction);
+                    sub = sub.wrapping_sub(free) & free;
+                    if sub == 0 {
+                        break;
+                    }
+                }
+            }
+
+            table
+        };
     };
 }
 
@@ -152,7 +429,7 @@ instruction_set!(
     /// ```asm
     /// LOAD R3, 0xff00
     /// ```
-    LOAD        0b110000_000_000_000_0      0b111111_000_000_000_0, // Data Manipulation Instruction
+    LOAD        0b110000_000_000_000_0      0b111111_000_000_000_0  RxAddress        DataManipulation, // Data Manipulation Instruction
 
     /// Carrega o valor `NR` no registrador `Rx`.
     ///
@@ -168,7 +445,7 @@ instruction_set!(
     /// ```asm
     /// LOADN R3, #0xff00
     /// ```
-    LOADN       0b111000_000_000_000_0      0b111111_000_000_000_0,
+    LOADN       0b111000_000_000_000_0      0b111111_000_000_000_0  RxImmediate      DataManipulation,
 
     /// Carrega o valor da memória presente no endereço armazenado em `Ry` para o registrador
     /// `Rx`.
@@ -185,7 +462,7 @@ instruction_set!(
     /// ```asm
     /// LOADI R3, R0
     /// ```
-    LOADI       0b111100_000_000_000_0      0b111111_000_000_000_0,
+    LOADI       0b111100_000_000_000_0      0b111111_000_000_000_0  RxRy             DataManipulation,
 
     /// Salva no endereço `END` da memória o valor presente no registrador `Rx`.
     ///
@@ -201,7 +478,7 @@ instruction_set!(
     /// ```asm
     /// STORE 0x00ff, R3
     /// ```
-    STORE       0b110001_000_000_000_0      0b111111_000_000_000_0,
+    STORE       0b110001_000_000_000_0      0b111111_000_000_000_0  AddressRx        DataManipulation,
 
     /// Salva no endereço `END` da memória o valor `NR`.
     ///
@@ -217,7 +494,7 @@ instruction_set!(
     /// ```asm
     /// STOREN 0x00ff, #0b10100
     /// ```
-    STOREN      0b111001_000_000_000_0      0b111111_000_000_000_0,
+    STOREN      0b111001_000_000_000_0      0b111111_000_000_000_0  AddressImmediate DataManipulation,
 
     /// Salva, na memória, no endereço armazenado em `Rx`, o valor presente no registrador `Ry`.
     ///
@@ -233,7 +510,7 @@ instruction_set!(
     /// ```asm
     /// STOREI R3, R0
     /// ```
-    STOREI      0b111101_000_000_000_0      0b111111_000_000_000_0,
+    STOREI      0b111101_000_000_000_0      0b111111_000_000_000_0  RxRy             DataManipulation,
 
     /// Move, para um registrador `Rx` ou para o `SP`, o valor presente em outro registrador.
     ///
@@ -255,10 +532,10 @@ instruction_set!(
     /// MOV R3, SP
     /// MOV SP, R0
     /// ```
-    MOV         0b110011_000_000_000_0      0b111111_000_000_000_0,
+    MOV         0b110011_000_000_000_0      0b111111_000_000_000_0  Mov              DataManipulation,
 
-    INPUT       0b111110_000_000_000_0      0b111111_000_000_000_0, // Peripheric Instructions
-    OUTPUT      0b111111_000_000_000_0      0b111111_000_000_000_0,
+    INPUT       0b111110_000_000_000_0      0b111111_000_000_000_0  RxRy             Peripheral, // Peripheric Instructions
+    OUTPUT      0b111111_000_000_000_0      0b111111_000_000_000_0  RxRy             Peripheral,
 
     /// Imprime na tela do processador um *char* mapeado de um arquivo *charmap*. O código do
     /// *pixelmap* que representa o desenho do *char* está codificado no *low-byte* do registrador
@@ -301,10 +578,10 @@ instruction_set!(
     /// ```asm
     /// OUTCHAR R1, R0
     /// ```
-    OUTCHAR     0b110010_000_000_000_0      0b111111_000_000_000_0, // IO Instructions
+    OUTCHAR     0b110010_000_000_000_0      0b111111_000_000_000_0  RxRy             Io, // IO Instructions
 
-    INCHAR      0b110101_000_000_000_0      0b111111_000_000_000_0,
-    SOUND       0b110100_000_000_000_0      0b111111_000_000_000_0,
+    INCHAR      0b110101_000_000_000_0      0b111111_000_000_000_0  Rx               Io,
+    SOUND       0b110100_000_000_000_0      0b111111_000_000_000_0  Rx               Io,
 
     /// Realiza a soma dos valores presentes nos registradores `Ry` e `Rz`, guardando o resultado
     /// no registrador `Rx`.
@@ -321,7 +598,7 @@ instruction_set!(
     /// ```asm
     /// ADD R3, R0, R7
     /// ```
-    ADD         0b100000_000_000_000_0      0b111111_000_000_000_1, // Aritmethic Instructions
+    ADD         0b100000_000_000_000_0      0b111111_000_000_000_1  RxRyRz           Arithmetic, // Aritmethic Instructions
 
     /// Realiza a soma dos valores presentes nos registradores `Ry` e `Rz` mais o *carry* (`C`),
     /// guardando o resultado no registrador `Rx`.
@@ -338,7 +615,7 @@ instruction_set!(
     /// ```asm
     /// ADDC R3, R0, R7
     /// ```
-    ADDC        0b100000_000_000_000_1      0b111111_000_000_000_1,
+    ADDC        0b100000_000_000_000_1      0b111111_000_000_000_1  RxRyRz           Arithmetic,
 
     /// Realiza a subtração dos valores presentes nos registradores `Ry` e `Rz`, guardando o
     /// resultado no registrador `Rx`.
@@ -355,7 +632,7 @@ instruction_set!(
     /// ```asm
     /// SUB R3, R0, R7
     /// ```
-    SUB         0b100001_000_000_000_0      0b111111_000_000_000_1,
+    SUB         0b100001_000_000_000_0      0b111111_000_000_000_1  RxRyRz           Arithmetic,
 
     /// Realiza a subtração dos valores presentes nos registradores `Ry` e `Rz`, guardando no
     /// registrador `Rx` o resultado somado com o *carry* (`C`).
@@ -372,7 +649,7 @@ instruction_set!(
     /// ```asm
     /// SUBC R3, R0, R7
     /// ```
-    SUBC        0b100001_000_000_000_1      0b111111_000_000_000_1,
+    SUBC        0b100001_000_000_000_1      0b111111_000_000_000_1  RxRyRz           Arithmetic,
 
     /// Realiza a multiplicação dos valores presentes nos registradores `Ry` e `Rz`, guardando o
     /// resultado no registrador `Rx`.
@@ -389,7 +666,7 @@ instruction_set!(
     /// ```asm
     /// MUL R3, R0, R7
     /// ```
-    MUL         0b100010_000_000_000_0      0b111111_000_000_000_1,
+    MUL         0b100010_000_000_000_0      0b111111_000_000_000_1  RxRyRz           Arithmetic,
 
     /// Realiza a divisão de `Ry` por `Rz`, guardando o resultado no registrador `Rx`.
     ///
@@ -405,7 +682,7 @@ instruction_set!(
     /// ```asm
     /// DIV R3, R0, R7
     /// ```
-    DIV         0b100011_000_000_000_0     0b111111_000_000_000_1,
+    DIV         0b100011_000_000_000_0     0b111111_000_000_000_1  RxRyRz           Arithmetic,
 
     /// Incrementa em uma unidade o registrador `Rx`.
     ///
@@ -421,7 +698,7 @@ instruction_set!(
     /// ```asm
     /// INC R3
     /// ```
-    INC         0b100100_000_000_000_0      0b111111_000_100_000_0,
+    INC         0b100100_000_000_000_0      0b111111_000_100_000_0  Rx               Arithmetic,
 
     /// Decrementa em uma unidade o registrador `Rx`.
     ///
@@ -437,7 +714,7 @@ instruction_set!(
     /// ```asm
     /// DEC R3
     /// ```
-    DEC         0b100100_000_100_000_0      0b111111_000_100_000_0,
+    DEC         0b100100_000_100_000_0      0b111111_000_100_000_0  Rx               Arithmetic,
 
     /// Realiza a operação de módulo entre os registradores `Ry` e `Rz` e salva o resultado no
     /// registrador `Rx`.
@@ -454,7 +731,7 @@ instruction_set!(
     /// ```asm
     /// MOD R3, R2, R5
     /// ```
-    MOD         0b100101_000_000_000_0      0b111111_000_000_000_0,
+    MOD         0b100101_000_000_000_0      0b111111_000_000_000_0  RxRyRz           Arithmetic,
 
     /// Realiza a operação *AND* entre os registradores `Ry` e `Rz` e salva o resultado no
     /// registrador `Rx`.
@@ -471,7 +748,7 @@ instruction_set!(
     /// ```asm
     /// AND R3, R2, R5
     /// ```
-    AND         0b010010_000_000_000_0      0b111111_000_000_000_0, // Logic Instructions
+    AND         0b010010_000_000_000_0      0b111111_000_000_000_0  RxRyRz           Logic, // Logic Instructions
 
     /// Realiza a operação *OR* entre os registradores `Ry` e `Rz` e salva o resultado no
     /// registrador `Rx`.
@@ -488,7 +765,7 @@ instruction_set!(
     /// ```asm
     /// OR R3, R2, R5
     /// ```
-    OR          0b010011_000_000_000_0      0b111111_000_000_000_0,
+    OR          0b010011_000_000_000_0      0b111111_000_000_000_0  RxRyRz           Logic,
 
     /// Realiza a operação *XOR* entre os registradores `Ry` e `Rz` e salva o resultado no
     /// registrador `Rx`.
@@ -505,7 +782,7 @@ instruction_set!(
     /// ```asm
     /// XOR R3, R2, R5
     /// ```
-    XOR         0b010100_000_000_000_0      0b111111_000_000_000_0,
+    XOR         0b010100_000_000_000_0      0b111111_000_000_000_0  RxRyRz           Logic,
 
     /// Realiza a operação *NOT* no registrador `Ry` e salva o resultado no registrador `Rx`.
     ///
@@ -521,7 +798,7 @@ instruction_set!(
     /// ```asm
     /// NOT R3, R2
     /// ```
-    NOT         0b010101_000_000_000_0      0b111111_000_000_000_0,
+    NOT         0b010101_000_000_000_0      0b111111_000_000_000_0  RxRy             Logic,
 
     /// Esta operação desliza os bits para a esquerda `N` vezes e os bits que transbordam a
     /// extremidade esquerda desaparecem. Os espaços na direita são preenchidos com 0.
@@ -543,7 +820,7 @@ instruction_set!(
     /// ```asm
     /// SHIFTL0 R7, 9
     /// ```
-    SHIFTL0     0b010000_000_000_000_0      0b111111_000_111_000_0,
+    SHIFTL0     0b010000_000_000_000_0      0b111111_000_111_000_0  Shift            Logic,
 
     /// Esta operação desliza os bits para a esquerda `N` vezes e os bits que transbordam a
     /// extremidade esquerda desaparecem. Os espaços na direita são preenchidos com 1.
@@ -565,7 +842,7 @@ instruction_set!(
     /// ```asm
     /// SHIFTL1 R7, 9
     /// ```
-    SHIFTL1     0b010000_000_001_000_0      0b111111_000_111_000_0,
+    SHIFTL1     0b010000_000_001_000_0      0b111111_000_111_000_0  Shift            Logic,
 
     /// Esta operação desliza os bits para a direita `N` vezes e os bits que transbordam a
     /// extremidade direita desaparecem. Os espaços na esquerda são preenchidos com 0.
@@ -587,7 +864,7 @@ instruction_set!(
     /// ```asm
     /// SHIFTR0 R7, 9
     /// ```
-    SHIFTR0     0b010000_000_010_000_0      0b111111_000_111_000_0,
+    SHIFTR0     0b010000_000_010_000_0      0b111111_000_111_000_0  Shift            Logic,
 
     /// Esta operação desliza os bits para a direita `N` vezes e os bits que transbordam a
     /// extremidade direita desaparecem. Os espaços na esquerda são preenchidos com 1.
@@ -609,7 +886,7 @@ instruction_set!(
     /// ```asm
     /// SHIFTR1 R7, 9
     /// ```
-    SHIFTR1     0b010000_000_011_000_0      0b111111_000_111_000_0,
+    SHIFTR1     0b010000_000_011_000_0      0b111111_000_111_000_0  Shift            Logic,
 
     /// Esta operação gira os bits para a esquerda `N` vezes e os bits que transbordam para
     /// a extremidade esquerda são reintroduzidos no lado direito.
@@ -632,7 +909,7 @@ instruction_set!(
     /// ```asm
     /// ROTL R6, 2
     /// ```
-    ROTL        0b010000_000_100_000_0      0b111111_000_110_000_0,
+    ROTL        0b010000_000_100_000_0      0b111111_000_110_000_0  Shift            Logic,
 
     /// Esta operação gira os bits para a direita `N` vezes e os bits que transbordam para
     /// a extremidade direita são reintroduzidos no lado esquerdo.
@@ -655,7 +932,7 @@ instruction_set!(
     /// ```asm
     /// ROTL R6, 2
     /// ```
-    ROTR        0b010000_000_110_000_0      0b111111_000_110_000_0,
+    ROTR        0b010000_000_110_000_0      0b111111_000_110_000_0  Shift            Logic,
 
     /// Compara os valores dos registradores `Rx` e `Ry` e atualiza o *flag register* (`FR`) de
     /// acordo com o resultado.
@@ -672,7 +949,7 @@ instruction_set!(
     /// ```asm
     /// CMP R3, R2
     /// ```
-    CMP         0b010110_000_000_000_0      0b111111_000_000_000_0,
+    CMP         0b010110_000_000_000_0      0b111111_000_000_000_0  RxRy             Logic,
 
     /// Pula para o endereço `END` da memória.
     ///
@@ -688,7 +965,7 @@ instruction_set!(
     /// ```asm
     /// JMP 0x00ff
     /// ```
-    JMP         0b000010_000_000_000_0      0b111111_111_100_000_0,
+    JMP         0b000010_000_000_000_0      0b111111_111_100_000_0  Address          Branch, // Branch Instructions
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::EQUAL`] do
     /// *flag register* estiver setado.
@@ -705,7 +982,7 @@ instruction_set!(
     /// ```asm
     /// JEQ 0x00ff
     /// ```
-    JEQ         0b000010_000_100_000_0      0b111111_111_100_000_0,
+    JEQ         0b000010_000_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::EQUAL`] do
     /// *flag register* não estiver setado.
@@ -722,7 +999,7 @@ instruction_set!(
     /// ```asm
     /// JNE 0x00ff
     /// ```
-    JNE         0b000010_001_000_000_0      0b111111_111_100_000_0,
+    JNE         0b000010_001_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::ZERO`] do
     /// *flag register* estiver setado.
@@ -739,7 +1016,7 @@ instruction_set!(
     /// ```asm
     /// JZ 0x00ff
     /// ```
-    JZ          0b000010_001_100_000_0      0b111111_111_100_000_0,
+    JZ          0b000010_001_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::ZERO`] do
     /// *flag register* não estiver setado.
@@ -756,7 +1033,7 @@ instruction_set!(
     /// ```asm
     /// JNZ 0x00ff
     /// ```
-    JNZ         0b000010_010_000_000_0      0b111111_111_100_000_0,
+    JNZ         0b000010_010_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::CARRY`] do
     /// *flag register* estiver setado.
@@ -773,7 +1050,7 @@ instruction_set!(
     /// ```asm
     /// JC 0x00ff
     /// ```
-    JC          0b000010_010_100_000_0      0b111111_111_100_000_0,
+    JC          0b000010_010_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::CARRY`] do
     /// *flag register* não estiver setado.
@@ -790,7 +1067,7 @@ instruction_set!(
     /// ```asm
     /// JNC 0x00ff
     /// ```
-    JNC         0b000010_011_000_000_0      0b111111_111_100_000_0,
+    JNC         0b000010_011_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::GREATER`] do
     /// *flag register* estiver setado.
@@ -807,7 +1084,7 @@ instruction_set!(
     /// ```asm
     /// JGR 0x00ff
     /// ```
-    JGR         0b000010_011_100_000_0      0b111111_111_100_000_0,
+    JGR         0b000010_011_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::LESSER`] do
     /// *flag register* estiver setado.
@@ -824,7 +1101,7 @@ instruction_set!(
     /// ```asm
     /// JLE 0x00ff
     /// ```
-    JLE         0b000010_100_000_000_0      0b111111_111_100_000_0,
+    JLE         0b000010_100_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** algum dos *bits* [`FlagIndex::GREATER`] ou
     /// [`FlagIndex::EQUAL`] do *flag register* estiver setado.
@@ -841,7 +1118,7 @@ instruction_set!(
     /// ```asm
     /// JEG 0x00ff
     /// ```
-    JEG         0b000010_100_100_000_0      0b111111_111_100_000_0,
+    JEG         0b000010_100_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** algum dos *bits* [`FlagIndex::LESSER`] ou
     /// [`FlagIndex::EQUAL`] do *flag register* estiver setado.
@@ -858,7 +1135,7 @@ instruction_set!(
     /// ```asm
     /// JEL 0x00ff
     /// ```
-    JEL         0b000010_101_000_000_0      0b111111_111_100_000_0,
+    JEL         0b000010_101_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::ARITHMETIC_OVERFLOW`] do
     /// *flag register* estiver setado.
@@ -875,7 +1152,7 @@ instruction_set!(
     /// ```asm
     /// JOV 0x00ff
     /// ```
-    JOV         0b000010_101_100_000_0      0b111111_111_100_000_0,
+    JOV         0b000010_101_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::ARITHMETIC_OVERFLOW`] do
     /// *flag register* não estiver setado.
@@ -892,7 +1169,7 @@ instruction_set!(
     /// ```asm
     /// JNO 0x00ff
     /// ```
-    JNO         0b000010_110_000_000_0      0b111111_111_100_000_0,
+    JNO         0b000010_110_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::DIV_BY_ZERO`] do
     /// *flag register* estiver setado.
@@ -909,7 +1186,7 @@ instruction_set!(
     /// ```asm
     /// JDZ 0x00ff
     /// ```
-    JDZ         0b000010_110_100_000_0      0b111111_111_100_000_0,
+    JDZ         0b000010_110_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Pula para o endereço `END` da memória **se** o *bit* [`FlagIndex::NEGATIVE`] do
     /// *flag register* estiver setado.
@@ -926,7 +1203,7 @@ instruction_set!(
     /// ```asm
     /// JN 0x00ff
     /// ```
-    JN          0b000010_111_000_000_0      0b111111_111_100_000_0,
+    JN          0b000010_111_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado.
     ///
@@ -944,7 +1221,7 @@ instruction_set!(
     /// ```asm
     /// CALL 0x003C
     /// ```
-    CALL        0b000011_000_000_000_0     0b111111_111_100_000_0,
+    CALL        0b000011_000_000_000_0     0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::EQUAL`] do *flag register* estiver setado.
@@ -963,7 +1240,7 @@ instruction_set!(
     /// ```asm
     /// CEQ 0x003C
     /// ```
-    CEQ         0b000011_000_100_000_0      0b111111_111_100_000_0,
+    CEQ         0b000011_000_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::EQUAL`] do *flag register* não estiver setado.
@@ -982,7 +1259,7 @@ instruction_set!(
     /// ```asm
     /// CNE 0x003C
     /// ```
-    CNE         0b000011_001_000_000_0      0b111111_111_100_000_0,
+    CNE         0b000011_001_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::ZERO`] do *flag register* estiver setado.
@@ -1001,7 +1278,7 @@ instruction_set!(
     /// ```asm
     /// CZ 0x003C
     /// ```
-    CZ          0b000011_001_100_000_0      0b111111_111_100_000_0,
+    CZ          0b000011_001_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::ZERO`] do *flag register* não estiver setado.
@@ -1020,7 +1297,7 @@ instruction_set!(
     /// ```asm
     /// CNZ 0x003C
     /// ```
-    CNZ         0b000011_010_000_000_0      0b111111_111_100_000_0,
+    CNZ         0b000011_010_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::CARRY`] do *flag register* estiver setado.
@@ -1039,7 +1316,7 @@ instruction_set!(
     /// ```asm
     /// CC 0x003C
     /// ```
-    CC          0b000011_010_100_000_0      0b111111_111_100_000_0,
+    CC          0b000011_010_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::CARRY`] do *flag register* não estiver setado.
@@ -1058,7 +1335,7 @@ instruction_set!(
     /// ```asm
     /// CNC 0x003C
     /// ```
-    CNC         0b000011_011_000_000_0      0b111111_111_100_000_0,
+    CNC         0b000011_011_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::GREATER`] do *flag register* estiver setado.
@@ -1077,7 +1354,7 @@ instruction_set!(
     /// ```asm
     /// CGR 0x003C
     /// ```
-    CGR         0b000011_011_100_000_0      0b111111_111_100_000_0,
+    CGR         0b000011_011_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::LESSER`] do *flag register* estiver setado.
@@ -1096,7 +1373,7 @@ instruction_set!(
     /// ```asm
     /// CLE 0x003C
     /// ```
-    CLE         0b000011_100_000_000_0      0b111111_111_100_000_0,
+    CLE         0b000011_100_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// algum dos *bits* [`FlagIndex::EQUAL`] ou [`FlagIndex::GREATER`] do *flag register* estiver
@@ -1116,7 +1393,7 @@ instruction_set!(
     /// ```asm
     /// CEG 0x003C
     /// ```
-    CEG         0b000011_100_100_000_0      0b111111_111_100_000_0,
+    CEG         0b000011_100_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// algum dos *bits* [`FlagIndex::EQUAL`] ou [`FlagIndex::LESSER`] do *flag register* estiver
@@ -1136,7 +1413,7 @@ instruction_set!(
     /// ```asm
     /// CEL 0x003C
     /// ```
-    CEL         0b000011_101_000_000_0      0b111111_111_100_000_0,
+    CEL         0b000011_101_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::ARITHMETIC_OVERFLOW`] do *flag register* estiver setado.
@@ -1155,7 +1432,7 @@ instruction_set!(
     /// ```asm
     /// COV 0x003C
     /// ```
-    COV         0b000011_101_100_000_0      0b111111_111_100_000_0,
+    COV         0b000011_101_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::ARITHMETIC_OVERFLOW`] do *flag register* não estiver setado.
@@ -1174,7 +1451,7 @@ instruction_set!(
     /// ```asm
     /// CNO 0x003C
     /// ```
-    CNO         0b000011_110_000_000_0      0b111111_111_100_000_0,
+    CNO         0b000011_110_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::DIV_BY_ZERO`] do *flag register* estiver setado.
@@ -1193,7 +1470,7 @@ instruction_set!(
     /// ```asm
     /// CDZ 0x003C
     /// ```
-    CDZ         0b000011_110_100_000_0      0b111111_111_100_000_0,
+    CDZ         0b000011_110_100_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Salva o valor atual do *PC* na *stack* e pula para o endereço do procedimento informado se
     /// o *bit* [`FlagIndex::NEGATIVE`] do *flag register* estiver setado.
@@ -1212,7 +1489,7 @@ instruction_set!(
     /// ```asm
     /// CN 0x003C
     /// ```
-    CN          0b000011_111_000_000_0      0b111111_111_100_000_0,
+    CN          0b000011_111_000_000_0      0b111111_111_100_000_0  Address          Branch,
 
     /// Altera o valor do *PC* para o último valor salvo na *stack* somado de 1.
     ///
@@ -1230,7 +1507,7 @@ instruction_set!(
     /// ```asm
     /// RTS
     /// ```
-    RTS         0b000100_000_000_000_0      0b111111_000_000_000_1,
+    RTS         0b000100_000_000_000_0      0b111111_000_000_000_1  None             Branch,
 
     /// Altera o valor do *PC* para o último valor salvo na *stack*.
     ///
@@ -1247,7 +1524,7 @@ instruction_set!(
     /// ```asm
     /// RTI
     /// ```
-    RTI         0b000100_000_000_000_1      0b111111_000_000_000_1,
+    RTI         0b000100_000_000_000_1      0b111111_000_000_000_1  None             Branch,
 
     /// Salva na *stack* o conteúdo de um registrador ou do *flag register*.
     ///
@@ -1268,7 +1545,7 @@ instruction_set!(
     /// PUSH R5
     /// PUSH FR
     /// ```
-    PUSH        0b000101_000_000_000_0      0b111111_000_000_000_0,
+    PUSH        0b000101_000_000_000_0      0b111111_000_000_000_0  Stack            Stack, // Stack Instructions
 
     /// Recupera da *stack* o conteúdo de um registrador ou do *flag register*.
     ///
@@ -1289,7 +1566,7 @@ instruction_set!(
     /// POP R5
     /// POP FR
     /// ```
-    POP         0b000110_000_000_000_0      0b111111_000_000_000_0,
+    POP         0b000110_000_000_000_0      0b111111_000_000_000_0  Stack            Stack,
 
     /// Sem operação. Serve apenas para consumir tempo.
     ///
@@ -1305,7 +1582,7 @@ instruction_set!(
     /// ```asm
     /// NOP
     /// ```
-    NOP         0b000000_000_000_000_0      0b111111_000_000_000_0, // Control Instructions
+    NOP         0b000000_000_000_000_0      0b111111_000_000_000_0  None             Control, // Control Instructions
 
     /// Para a execução do programa.
     ///
@@ -1321,7 +1598,7 @@ instruction_set!(
     /// ```asm
     /// HALT
     /// ```
-    HALT        0b001111_000_000_000_0      0b111111_000_000_000_0,
+    HALT        0b001111_000_000_000_0      0b111111_000_000_000_0  None             Control,
 
     /// Limpa o bit [`FlagIndex::CARRY`] do *flag register*.
     ///
@@ -1337,7 +1614,7 @@ instruction_set!(
     /// ```asm
     /// CLEARC
     /// ```
-    CLEARC      0b001000_000_000_000_0      0b111111_100_000_000_0,
+    CLEARC      0b001000_000_000_000_0      0b111111_100_000_000_0  None             Control,
 
     /// Seta o bit [`FlagIndex::CARRY`] do *flag register*.
     ///
@@ -1353,7 +1630,7 @@ instruction_set!(
     /// ```asm
     /// SETC
     /// ```
-    SETC        0b001000_100_000_000_0      0b111111_100_000_000_0,
+    SETC        0b001000_100_000_000_0      0b111111_100_000_000_0  None             Control,
 
     /// Gera um *breakpoint* no código, forçando o simulador a entrar no modo *debug*.
     ///
@@ -1369,9 +1646,10 @@ instruction_set!(
     /// ```asm
     /// BREAKP
     /// ```
-    BREAKP      0b001110_000_000_000_0      0b111111_000_000_000_0
+    BREAKP      0b001110_000_000_000_0      0b111111_000_000_000_0  None             Control
 );
 
+#[allow(clippy::derivable_impls)]
 impl Default for Instruction {
     fn default() -> Self {
         Self::NOP
@@ -1397,12 +1675,99 @@ mod tests {
         assert_eq!(inst.opcode(), 0b110000);
     }
 
+    #[test]
+    fn test_all() {
+        assert_eq!(Instruction::COUNT, 71);
+        assert_eq!(Instruction::ALL.last(), Some(&Instruction::BREAKP));
+
+        for (i, a) in Instruction::ALL.iter().enumerate() {
+            assert!(!Instruction::ALL[i + 1..].contains(a));
+        }
+
+        let shifts: Vec<Instruction> = Instruction::with_opcode(0b010000).collect();
+        assert_eq!(
+            shifts,
+            vec![
+                Instruction::SHIFTL0,
+                Instruction::SHIFTL1,
+                Instruction::SHIFTR0,
+                Instruction::SHIFTR1,
+                Instruction::ROTL,
+                Instruction::ROTR
+            ]
+        );
+        assert_eq!(Instruction::with_opcode(0b000111).count(), 0);
+    }
+
+    #[test]
+    fn test_masks_overlap() {
+        let add = (0b100000_000_000_000_0, 0b111111_000_000_000_1);
+        let addc = (0b100000_000_000_000_1, 0b111111_000_000_000_1);
+        let modulo = (0b100101_000_000_000_0, 0b111111_000_000_000_0);
+        let sub_ignoring_carry = (0b100001_000_000_000_0, 0b111111_000_000_000_0);
+        let subc = (0b100001_000_000_000_1, 0b111111_000_000_000_1);
+
+        assert!(!masks_overlap(add.0, add.1, addc.0, addc.1));
+        assert!(!masks_overlap(add.0, add.1, modulo.0, modulo.1));
+        assert!(masks_overlap(
+            sub_ignoring_carry.0,
+            sub_ignoring_carry.1,
+            subc.0,
+            subc.1
+        ));
+    }
+
+    #[test]
+    fn test_decode_table_matches_linear_decoder() {
+        for v in (0..=u16::MAX).map(Word) {
+            assert_eq!(
+                Instruction::get_instruction(v),
+                Instruction::get_instruction_linear(v),
+                "{v:#018b}"
+            );
+        }
+    }
+
     #[test]
     fn test_get_instruction() {
-        let code = 0b100000_111_000_000_1; // ADDC
+        let code = Word(0b100000_111_000_000_1); // ADDC
         assert_eq!(
             Instruction::get_instruction(code).unwrap(),
             Instruction::ADDC
         );
     }
+
+    #[test]
+    fn test_invalid_instruction() {
+        let err = Instruction::get_instruction(Word(0b000011_111_100_000_1)).unwrap_err();
+        assert_eq!(err.address(), None);
+        assert_eq!(
+            err.to_string(),
+            "Instrução inválida: 0x0fc1 (000011_111_100_000_1); mais próximas: CGR, COV, CDZ, CN"
+        );
+
+        let err = Instruction::get_instruction(Word(0b000111_000_000_000_0)).unwrap_err();
+        assert!(err.nearest().is_empty());
+        assert_eq!(
+            err.to_string(),
+            "Instrução inválida: 0x1c00 (000111_000_000_000_0)"
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_get_instruction_usize() {
+        assert_eq!(
+            Instruction::get_instruction_usize(0b100000_111_000_000_1).unwrap(),
+            Instruction::ADDC
+        );
+
+        let code = 0b1_100000_111_000_000_1; // ADDC com um bit além da palavra
+        assert_eq!(
+            Instruction::get_instruction_usize(code),
+            Err(UsizeDecodeError::WordOutOfRange(WordOutOfRange {
+                value: code
+            }))
+        );
+    }
 }
@@ -0,0 +1,386 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use thiserror::Error;
+
+use crate::locale::{key, message};
+use crate::{
+    AsmStyle, DecodeMode, DecodedInstruction, Disassembled, Disassembler, Instruction, Locale,
+    Localize, Word, MEMORY_SIZE,
+};
+
+/// Uma região de uma [`Listing`], começando no endereço `address`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Region {
+    /// Uma instrução alcançável a partir do endereço 0.
+    Code {
+        address: Word,
+        decoded: DecodedInstruction,
+    },
+    /// Palavras de dados, escritas como `var #N` e `static`.
+    Var { address: Word, values: Vec<Word> },
+    /// Uma *string* terminada em `\0`, escrita como `string "..."`.
+    String { address: Word, text: String },
+}
+
+impl Region {
+    /// Retorna o endereço do início da região.
+    pub fn address(&self) -> Word {
+        match self {
+            Region::Code { address, .. }
+            | Region::Var { address, .. }
+            | Region::String { address, .. } => *address,
+        }
+    }
+}
+
+/// *Disassembly* de uma imagem da memória que separa o código dos dados e dá nomes aos
+/// endereços. O código é encontrado seguindo o fluxo de controle a partir do endereço 0; as
+/// demais palavras são escritas como dados. O texto gerado, quando montado, reproduz a mesma
+/// imagem.
+///
+/// ## Exemplo
+///
+/// ```
+/// use isa::*;
+///
+/// let mem = [
+///     0b000010_000_000_000_0, // JMP 0x0005
+///     0x0005,
+///     'O' as u16,
+///     'i' as u16,
+///     0,
+///     0b001111_000_000_000_0, // HALT
+/// ]
+/// .map(Word);
+///
+/// assert_eq!(
+///     "    JMP L0005
+/// S0002:
+///     string \"Oi\"
+/// L0005:
+///     HALT
+/// ",
+///     Listing::new(&mem).unwrap().to_string()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listing {
+    regions: Vec<Region>,
+    labels: BTreeMap<Word, String>,
+}
+
+/// Erro retornado por [`Listing::new`] quando a imagem tem mais palavras que a memória.
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub struct ImageTooLarge {
+    pub len: usize,
+}
+
+impl Localize for ImageTooLarge {
+    fn localize(&self, locale: Locale) -> String {
+        message(locale, key::IMAGE_TOO_LARGE, &[("len", &self.len)])
+    }
+}
+
+/// Retorna a palavra com o endereço `address` de uma imagem já validada por [`Listing::new`].
+fn word(address: usize) -> Word {
+    Word::try_from(address).expect("endereços da memória cabem em uma palavra")
+}
+
+/// Indica se a palavra é um caractere que pode ser escrito em uma *string* sem escapes.
+fn is_printable(v: Word) -> bool {
+    (0x20..=0x7e).contains(&v.0)
+}
+
+/// Retorna os endereços das instruções alcançáveis a partir do endereço 0.
+fn reachable(disassembler: &Disassembler<'_>) -> BTreeMap<usize, DecodedInstruction> {
+    let mut code = BTreeMap::new();
+    let mut covered = BTreeSet::new();
+    let mut pending = vec![0];
+
+    while let Some(address) = pending.pop() {
+        if code.contains_key(&address) {
+            continue;
+        }
+
+        let Some(Disassembled::Instruction(decoded)) = disassembler.disassemble_at(address) else {
+            continue;
+        };
+
+        let words = address..address + decoded.instruction.size();
+        if words.clone().any(|word| covered.contains(&word)) {
+            continue;
+        }
+
+        covered.extend(words);
+        code.insert(address, decoded);
+
+        let stops = decoded.instruction == Instruction::JMP
+            || decoded.instruction == Instruction::HALT
+            || decoded.instruction.is_return();
+
+        if !stops {
+            pending.push(address + decoded.instruction.size());
+        }
+
+        if let (Some(_), Some(target)) = (decoded.condition(), decoded.address) {
+            pending.push(usize::from(target));
+        }
+    }
+
+    code
+}
+
+impl Listing {
+    /// Separa `mem` em código e dados e gera os rótulos dos alvos de pulos e chamadas. Imagens
+    /// com mais de [`MEMORY_SIZE`] palavras retornam [`ImageTooLarge`].
+    pub fn new(mem: &[Word]) -> Result<Self, ImageTooLarge> {
+        if mem.len() > MEMORY_SIZE {
+            return Err(ImageTooLarge { len: mem.len() });
+        }
+
+        // Apenas codificações canônicas são tratadas como código, para que a montagem do texto
+        // reproduza exatamente as mesmas palavras.
+        let disassembler = Disassembler::new(mem).with_mode(DecodeMode::Strict);
+        let code = reachable(&disassembler);
+
+        let mut regions = vec![];
+        let mut address = 0;
+
+        while address < mem.len() {
+            if let Some(&decoded) = code.get(&address) {
+                regions.push(Region::Code {
+                    address: word(address),
+                    decoded,
+                });
+                address += decoded.instruction.size();
+                continue;
+            }
+
+            let end = (address..mem.len())
+                .find(|a| code.contains_key(a))
+                .unwrap_or(mem.len());
+            Listing::data(&mem[address..end], address, &mut regions);
+            address = end;
+        }
+
+        let mut labels = BTreeMap::new();
+
+        for region in &regions {
+            match region {
+                Region::Code { decoded, .. } => {
+                    let target = decoded.address.filter(|_| decoded.condition().is_some());
+
+                    if let Some(target) = target.filter(|t| code.contains_key(&usize::from(*t))) {
+                        labels.insert(target, format!("L{:04x}", target.0));
+                    }
+                }
+                Region::Var { address, .. } => {
+                    labels.insert(*address, format!("D{:04x}", address.0));
+                }
+                Region::String { address, .. } => {
+                    labels.insert(*address, format!("S{:04x}", address.0));
+                }
+            }
+        }
+
+        Ok(Self { regions, labels })
+    }
+
+    /// Separa as palavras de dados `data`, que começam no endereço `start`, em *strings* e
+    /// variáveis.
+    fn data(data: &[Word], start: usize, regions: &mut Vec<Region>) {
+        let mut values = vec![];
+        let mut i = 0;
+
+        while i < data.len() {
+            let len = data[i..].iter().take_while(|v| is_printable(**v)).count();
+
+            if len >= 2 && data.get(i + len) == Some(&Word(0)) {
+                if !values.is_empty() {
+                    let address = word(start + i - values.len());
+                    regions.push(Region::Var {
+                        address,
+                        values: std::mem::take(&mut values),
+                    });
+                }
+
+                let text = data[i..i + len].iter().map(|v| v.0 as u8 as char).collect();
+                regions.push(Region::String {
+                    address: word(start + i),
+                    text,
+                });
+                i += len + 1;
+                continue;
+            }
+
+            values.push(data[i]);
+            i += 1;
+        }
+
+        if !values.is_empty() {
+            let address = word(start + i - values.len());
+            regions.push(Region::Var { address, values });
+        }
+    }
+
+    /// Retorna as regiões da memória, em ordem de endereço.
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    /// Retorna o rótulo gerado para o endereço `address`, se houver.
+    pub fn label(&self, address: Word) -> Option<&str> {
+        self.labels.get(&address).map(String::as_str)
+    }
+
+    /// Escreve o texto da *listing* com o estilo `style`.
+    pub fn render(&self, style: AsmStyle) -> String {
+        let mut text = String::new();
+
+        for region in &self.regions {
+            let address = region.address();
+
+            if let Some(label) = self.label(address) {
+                text += &format!("{label}:\n");
+            }
+
+            match region {
+                Region::Code { decoded, .. } => {
+                    let label = decoded
+                        .address
+                        .filter(|_| decoded.condition().is_some())
+                        .and_then(|target| self.label(target));
+
+                    let line = match label {
+                        Some(label) => decoded.display(style).with_address_label(label),
+                        None => decoded.display(style),
+                    };
+                    text += &format!("    {line}\n");
+                }
+                Region::Var { values, .. } => {
+                    let label = self.label(address).unwrap_or_default();
+                    text += &format!("    var #{}\n", values.len());
+
+                    for (offset, value) in values.iter().enumerate() {
+                        if *value != Word::ZERO {
+                            text += &format!(
+                                "    static {label} + #{offset}, #{}\n",
+                                style.number(*value)
+                            );
+                        }
+                    }
+                }
+                Region::String { text: string, .. } => {
+                    let escaped = string.replace('\\', "\\\\").replace('"', "\\\"");
+                    text += &format!("    string \"{escaped}\"\n");
+                }
+            }
+        }
+
+        text
+    }
+}
+
+impl std::fmt::Display for Listing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.render(AsmStyle::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reachability() {
+        let mem = [
+            0b000011_000_000_000_0, // CALL 0x0006
+            0x0006,
+            0b001111_000_000_000_0, // HALT
+            0b100000_011_000_111_0, // Inalcançável: ADD R3, R0, R7
+            0x0000,
+            0x0000,
+            0b000010_000_100_000_0, // JEQ 0x0002
+            0x0002,
+            0b000100_000_000_000_0, // RTS
+        ]
+        .map(Word);
+
+        let listing = Listing::new(&mem).unwrap();
+        let addresses: Vec<(u16, bool)> = listing
+            .regions()
+            .iter()
+            .map(|r| (r.address().0, matches!(r, Region::Code { .. })))
+            .collect();
+
+        assert_eq!(
+            addresses,
+            vec![(0, true), (2, true), (3, false), (6, true), (8, true)]
+        );
+        assert_eq!(listing.label(Word(2)), Some("L0002"));
+        assert_eq!(listing.label(Word(6)), Some("L0006"));
+        assert_eq!(listing.label(Word(3)), Some("D0003"));
+    }
+
+    #[test]
+    fn test_data_rendering() {
+        let mem = [
+            0b001111_000_000_000_0, // HALT
+            0x0005,
+            'a' as u16,
+            '"' as u16,
+            0,
+            0,
+        ]
+        .map(Word);
+
+        assert_eq!(
+            Listing::new(&mem).unwrap().to_string(),
+            "    HALT\nD0001:\n    var #1\n    static D0001 + #0, #0x0005\n\
+             S0002:\n    string \"a\\\"\"\nD0005:\n    var #1\n"
+        );
+    }
+
+    #[test]
+    fn test_non_canonical_words_are_data() {
+        let mem = [0b000100_101_000_000_0].map(Word); // RTS com o campo Rx setado
+        assert!(matches!(
+            Listing::new(&mem).unwrap().regions()[0],
+            Region::Var { .. }
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mem = [
+            0b000011_000_000_000_0, // CALL 0x0009
+            0x0009,
+            0b000010_000_100_000_0, // JEQ 0x0007
+            0x0007,
+            0b001111_000_000_000_0, // HALT
+            'a' as u16,
+            '\\' as u16,
+            0,
+            0b000100_101_000_000_0, // RTS com o campo Rx setado
+            0b111000_011_000_000_0, // LOADN R3, #0xffff
+            0xffff,
+            0b000100_000_000_000_0, // RTS
+            0,
+            0b000010_000_000_000_0, // JMP sem o endereço
+        ]
+        .map(Word);
+
+        let listing = Listing::new(&mem).unwrap().to_string();
+        assert_eq!(crate::assemble(&listing).unwrap(), mem, "{listing}");
+    }
+
+    #[test]
+    fn test_image_too_large() {
+        let mem = vec![Word(0); MEMORY_SIZE];
+        assert!(Listing::new(&mem).is_ok());
+
+        let mem = vec![Word(0); u16::MAX as usize + 2];
+        assert_eq!(Listing::new(&mem), Err(ImageTooLarge { len: 0x10001 }));
+    }
+}
@@ -0,0 +1,723 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::Instruction;
+
+/// Idioma das mensagens exibidas ao usuário.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    Portuguese,
+    English,
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub struct ParseLocaleError {
+    pub name: String,
+}
+
+impl FromStr for Locale {
+    type Err = ParseLocaleError;
+
+    /// Converte um código de idioma (`pt`, `pt-BR`, `en`, `en_US`, ...), sem diferenciar
+    /// maiúsculas de minúsculas.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// assert_eq!(Locale::English, "en_US".parse().unwrap());
+    /// assert_eq!(Locale::Portuguese, "pt-BR".parse().unwrap());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.trim().split(['-', '_']).next().unwrap_or_default();
+
+        match language.to_ascii_lowercase().as_str() {
+            "pt" => Ok(Locale::Portuguese),
+            "en" => Ok(Locale::English),
+            _ => Err(ParseLocaleError {
+                name: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// Mensagens que podem ser exibidas em qualquer [`Locale`]. Os textos vêm do catálogo de
+/// mensagens, e a implementação de [`std::fmt::Display`] dos tipos corresponde a
+/// [`Locale::Portuguese`].
+///
+/// ## Exemplo
+///
+/// ```
+/// use isa::*;
+///
+/// let err = Instruction::get_instruction(Word(0b000010_111_100_000_0)).unwrap_err();
+///
+/// assert_eq!(err.to_string(), err.localize(Locale::Portuguese));
+/// assert_eq!(
+///     "Invalid instruction: 0x0bc0 (000010_111_100_000_0); nearest: JGR, JOV, JDZ, JN",
+///     err.localize(Locale::English)
+/// );
+/// ```
+pub trait Localize {
+    /// Retorna a mensagem no idioma `locale`.
+    fn localize(&self, locale: Locale) -> String;
+}
+
+impl Localize for ParseLocaleError {
+    fn localize(&self, locale: Locale) -> String {
+        message(locale, key::UNKNOWN_LOCALE, &[("name", &self.name)])
+    }
+}
+
+/// Chave de uma mensagem do catálogo. As chaves só existem como as constantes de [`key`],
+/// geradas junto do catálogo, de modo que toda chave tem uma mensagem.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Key(&'static str);
+
+/// Define as constantes de [`key`] e o catálogo `MESSAGES` a partir da mesma lista. Cada entrada
+/// tem o nome da constante, a chave, o modelo em português e o modelo em inglês.
+macro_rules! catalog {
+    ($($name:ident = $key:literal: $portuguese:literal, $english:literal;)*) => {
+        /// Chaves do catálogo de mensagens.
+        pub(crate) mod key {
+            use super::Key;
+
+            $(pub(crate) const $name: Key = Key($key);)*
+        }
+
+        /// Catálogo de mensagens: a chave, o modelo em português e o modelo em inglês. Os
+        /// argumentos aparecem nos modelos como `{nome}`. As chaves dos erros de montagem são os
+        /// nomes retornados por [`AssembleErrorKind::name`](crate::AssembleErrorKind::name).
+        const MESSAGES: &[(Key, &str, &str)] = &[$((key::$name, $portuguese, $english)),*];
+    };
+}
+
+catalog! {
+    UNKNOWN_LOCALE = "unknown_locale":
+        "Idioma desconhecido: {name}",
+        "Unknown language: {name}";
+
+    WORD_OUT_OF_RANGE = "word_out_of_range":
+        "Valor não cabe em uma palavra: {value}",
+        "Value does not fit in a word: {value}";
+
+    DECODE_AT_OUT_OF_RANGE = "decode_at.out_of_range":
+        "Endereço fora da memória: {address} (a memória tem {len} palavras)",
+        "Address out of memory: {address} (the memory has {len} words)";
+
+    BITS_OUT_OF_RANGE = "bits_out_of_range":
+        "Intervalo de bits inválido: {start}..{end} em um valor de {width} bits",
+        "Invalid bit range: {start}..{end} in a {width}-bit value";
+
+    VALUE_TOO_WIDE = "value_too_wide":
+        "O valor não cabe em um campo de {len} bits",
+        "The value does not fit in a {len}-bit field";
+
+    UNKNOWN_REGISTER = "unknown_register":
+        "Registrador desconhecido: {name}",
+        "Unknown register: {name}";
+
+    UNKNOWN_MNEMONIC = "unknown_mnemonic":
+        "Mnemônico desconhecido: {mnemonic}",
+        "Unknown mnemonic: {mnemonic}";
+
+    UNKNOWN_MNEMONIC_SUGGESTIONS = "unknown_mnemonic.suggestions":
+        " (você quis dizer {suggestions}?)",
+        " (did you mean {suggestions}?)";
+
+    INVALID_INSTRUCTION = "invalid_instruction":
+        "Instrução inválida: {code} ({fields})",
+        "Invalid instruction: {code} ({fields})";
+
+    INVALID_INSTRUCTION_ADDRESS = "invalid_instruction.address":
+        " no endereço {address}",
+        " at address {address}";
+
+    INVALID_INSTRUCTION_NEAREST = "invalid_instruction.nearest":
+        "; mais próximas: {nearest}",
+        "; nearest: {nearest}";
+
+    ENCODE_INVALID_OPERANDS = "encode.invalid_operands":
+        "Operandos inválidos para a instrução {instruction}",
+        "Invalid operands for instruction {instruction}";
+
+    ENCODE_REGISTER_OUT_OF_RANGE = "encode.register_out_of_range":
+        "O registrador {register} não pode ser codificado em uma instrução",
+        "Register {register} cannot be encoded in an instruction";
+
+    ENCODE_SHIFT_OUT_OF_RANGE = "encode.shift_out_of_range":
+        "Deslocamento fora do intervalo: {n}",
+        "Shift out of range: {n}";
+
+    ENCODE_MISSING_ADDRESS = "encode.missing_address":
+        "A instrução {instruction} precisa de um endereço",
+        "Instruction {instruction} requires an address";
+
+    ENCODE_MISSING_IMMEDIATE = "encode.missing_immediate":
+        "A instrução {instruction} precisa de um valor imediato",
+        "Instruction {instruction} requires an immediate value";
+
+    ENCODE_UNEXPECTED_ADDRESS = "encode.unexpected_address":
+        "A instrução {instruction} não recebe um endereço",
+        "Instruction {instruction} does not take an address";
+
+    ENCODE_UNEXPECTED_IMMEDIATE = "encode.unexpected_immediate":
+        "A instrução {instruction} não recebe um valor imediato",
+        "Instruction {instruction} does not take an immediate value";
+
+    LOCATION = "location":
+        "Linha {line}, coluna {column}",
+        "Line {line}, column {column}";
+
+    LOCATION_FILE = "location.file":
+        "{file}, linha {line}, coluna {column}",
+        "{file}, line {line}, column {column}";
+
+    EXPANSION = "expansion":
+        "Na expansão da macro usada em {location}:",
+        "In the expansion of the macro used at {location}:";
+
+    OPERAND_COUNT = "operand_count":
+        "A instrução {instruction} espera {expected} operandos, mas recebeu {found}",
+        "Instruction {instruction} expects {expected} operands, but got {found}";
+
+    INVALID_OPERAND = "invalid_operand":
+        "Operando inválido para a instrução {instruction}: {operand}",
+        "Invalid operand for instruction {instruction}: {operand}";
+
+    INVALID_NUMBER = "invalid_number":
+        "Número inválido: {text}",
+        "Invalid number: {text}";
+
+    VALUE_OUT_OF_RANGE = "value_out_of_range":
+        "Valor não cabe em uma palavra: {value}",
+        "Value does not fit in a word: {value}";
+
+    INVALID_EXPRESSION = "invalid_expression":
+        "Expressão inválida: {text}",
+        "Invalid expression: {text}";
+
+    EXPRESSION_TOO_DEEP = "expression_too_deep":
+        "Expressão aninhada demais: {text}",
+        "Expression nested too deeply: {text}";
+
+    DIVISION_BY_ZERO = "division_by_zero":
+        "Divisão por zero",
+        "Division by zero";
+
+    NOT_CONSTANT = "not_constant":
+        "A expressão precisa ser constante: {text}",
+        "The expression must be constant: {text}";
+
+    CYCLIC_CONSTANT = "cyclic_constant":
+        "Definição circular da constante {name}",
+        "Circular definition of constant {name}";
+
+    INVALID_CHARACTER = "invalid_character":
+        "Caractere inválido: {text}",
+        "Invalid character: {text}";
+
+    INVALID_STRING = "invalid_string":
+        "String inválida: {text}",
+        "Invalid string: {text}";
+
+    INVALID_DIRECTIVE = "invalid_directive":
+        "Operandos inválidos para a diretiva {directive}: {operands}",
+        "Invalid operands for directive {directive}: {operands}";
+
+    ADDRESS_OUT_OF_RANGE = "address_out_of_range":
+        "Endereço fora da memória: {address}",
+        "Address out of memory: {address}";
+
+    INVALID_LABEL = "invalid_label":
+        "Rótulo inválido: {label}",
+        "Invalid label: {label}";
+
+    DUPLICATE_LABEL = "duplicate_label":
+        "Rótulo duplicado: {label}",
+        "Duplicate label: {label}";
+
+    UNDEFINED_LABEL = "undefined_label":
+        "Rótulo não definido: {label}",
+        "Undefined label: {label}";
+
+    INCLUDE = "include":
+        "Não foi possível ler o arquivo {path}: {message}",
+        "Could not read file {path}: {message}";
+
+    RECURSIVE_INCLUDE = "recursive_include":
+        "Inclusão recursiva do arquivo {path}",
+        "Recursive inclusion of file {path}";
+
+    DUPLICATE_MACRO = "duplicate_macro":
+        "Macro duplicada: {name}",
+        "Duplicate macro: {name}";
+
+    MACRO_ARGUMENT_COUNT = "macro_argument_count":
+        "A macro {name} espera {expected} argumentos, mas recebeu {found}",
+        "Macro {name} expects {expected} arguments, but got {found}";
+
+    RECURSIVE_MACRO = "recursive_macro":
+        "Expansão recursiva da macro {name}",
+        "Recursive expansion of macro {name}";
+
+    MACRO_EXPANSION_TOO_LARGE = "macro_expansion_too_large":
+        "A expansão da macro {name} ultrapassa o limite de {lines} linhas geradas por macros",
+        "Expanding macro {name} exceeds the limit of {lines} lines generated by macros";
+
+    UNEXPECTED_DIRECTIVE = "unexpected_directive":
+        "Diretiva {directive} inesperada",
+        "Unexpected directive {directive}";
+
+    UNTERMINATED_BLOCK = "unterminated_block":
+        "Falta o fim do bloco {directive}",
+        "Missing end of block {directive}";
+
+    EXPLAIN_TYPE = "explain.type":
+        "tipo",
+        "type";
+
+    EXPLAIN_MODE = "explain.mode":
+        "modo",
+        "mode";
+
+    EXPLAIN_SELECT = "explain.select":
+        "sel",
+        "sel";
+
+    EXPLAIN_CONDITION = "explain.condition":
+        "cond",
+        "cond";
+
+    EXPLAIN_INVALID = "explain.invalid":
+        "inválida",
+        "invalid";
+
+    EXPLAIN_IGNORED = "explain.ignored":
+        "ignorado",
+        "ignored";
+
+    PROGRAM_TOO_LARGE = "program_too_large":
+        "O programa não cabe na memória: {size} palavras",
+        "The program does not fit in memory: {size} words";
+
+    IMAGE_TOO_LARGE = "image_too_large":
+        "A imagem não cabe na memória: {len} palavras",
+        "The image does not fit in memory: {len} words";
+}
+
+/// Tradução para o inglês da documentação das instruções, cujo original em português está nos
+/// comentários de documentação de [`Instruction`]. As chaves são `description.MNEMÔNICO` e
+/// `operation.MNEMÔNICO`. Pulos e chamadas são descritos por `description.jump` ou
+/// `description.call` seguido de `condition.CONDIÇÃO`. Operações escritas apenas com símbolos
+/// não precisam de tradução.
+const DOCUMENTATION: &[(&str, &str)] = &[
+    (
+        "description.LOAD",
+        "Loads the value stored at memory address `END` into register `Rx`.",
+    ),
+    (
+        "description.LOADN",
+        "Loads the value `NR` into register `Rx`.",
+    ),
+    (
+        "description.LOADI",
+        "Loads the value stored at the memory address held in `Ry` into register `Rx`.",
+    ),
+    (
+        "description.STORE",
+        "Stores the value of register `Rx` at memory address `END`.",
+    ),
+    (
+        "description.STOREN",
+        "Stores the value `NR` at memory address `END`.",
+    ),
+    (
+        "description.STOREI",
+        "Stores the value of register `Ry` at the memory address held in `Rx`.",
+    ),
+    (
+        "description.MOV",
+        "Moves the value of another register into a register `Rx` or into the `SP`.",
+    ),
+    (
+        "operation.MOV",
+        "`Rx` ← `Ry` or\n`Rx` ← `SP` or\n`SP` ← `Rx`",
+    ),
+    (
+        "description.OUTCHAR",
+        "Prints on the processor screen a *char* mapped from a *charmap* file. The code of the \
+         *pixelmap* that draws the *char* is encoded in the *low-byte* of register `Rx`, while \
+         its color is in the *high-byte*. The position of the *char* is stored in register `Ry`.",
+    ),
+    ("operation.OUTCHAR", "VIDEO(`Ry`) ← CHAR(`Rx`)"),
+    (
+        "description.ADD",
+        "Adds the values of registers `Ry` and `Rz`, storing the result in register `Rx`.",
+    ),
+    (
+        "description.ADDC",
+        "Adds the values of registers `Ry` and `Rz` plus the *carry* (`C`), storing the result \
+         in register `Rx`.",
+    ),
+    (
+        "description.SUB",
+        "Subtracts the value of register `Rz` from `Ry`, storing the result in register `Rx`.",
+    ),
+    (
+        "description.SUBC",
+        "Subtracts the value of register `Rz` from `Ry`, storing in register `Rx` the result \
+         plus the *carry* (`C`).",
+    ),
+    (
+        "description.MUL",
+        "Multiplies the values of registers `Ry` and `Rz`, storing the result in register `Rx`.",
+    ),
+    (
+        "description.DIV",
+        "Divides `Ry` by `Rz`, storing the result in register `Rx`.",
+    ),
+    ("description.INC", "Increments register `Rx` by one."),
+    ("description.DEC", "Decrements register `Rx` by one."),
+    (
+        "description.MOD",
+        "Computes `Ry` modulo `Rz` and stores the result in register `Rx`.",
+    ),
+    (
+        "description.AND",
+        "Computes the *AND* of registers `Ry` and `Rz` and stores the result in register `Rx`.",
+    ),
+    (
+        "description.OR",
+        "Computes the *OR* of registers `Ry` and `Rz` and stores the result in register `Rx`.",
+    ),
+    (
+        "description.XOR",
+        "Computes the *XOR* of registers `Ry` and `Rz` and stores the result in register `Rx`.",
+    ),
+    (
+        "description.NOT",
+        "Computes the *NOT* of register `Ry` and stores the result in register `Rx`.",
+    ),
+    (
+        "description.SHIFTL0",
+        "Shifts the bits to the left `N` times; bits that overflow the left end are discarded. \
+         The vacated positions on the right are filled with 0.",
+    ),
+    (
+        "description.SHIFTL1",
+        "Shifts the bits to the left `N` times; bits that overflow the left end are discarded. \
+         The vacated positions on the right are filled with 1.",
+    ),
+    (
+        "description.SHIFTR0",
+        "Shifts the bits to the right `N` times; bits that overflow the right end are \
+         discarded. The vacated positions on the left are filled with 0.",
+    ),
+    (
+        "description.SHIFTR1",
+        "Shifts the bits to the right `N` times; bits that overflow the right end are \
+         discarded. The vacated positions on the left are filled with 1.",
+    ),
+    (
+        "description.ROTL",
+        "Rotates the bits to the left `N` times; bits that overflow the left end are reinserted \
+         on the right.",
+    ),
+    (
+        "description.ROTR",
+        "Rotates the bits to the right `N` times; bits that overflow the right end are \
+         reinserted on the left.",
+    ),
+    (
+        "description.CMP",
+        "Compares the values of registers `Rx` and `Ry` and updates the *flag register* (`FR`) \
+         accordingly.",
+    ),
+    ("description.jump", "Jumps to memory address `END`"),
+    (
+        "description.call",
+        "Saves the current value of the *PC* on the *stack* and jumps to the address of the \
+         given procedure",
+    ),
+    ("condition.Always", ""),
+    (
+        "condition.Equal",
+        " **if** the `EQUAL` bit of the flag register is set",
+    ),
+    (
+        "condition.NotEqual",
+        " **if** the `EQUAL` bit of the flag register is not set",
+    ),
+    (
+        "condition.Zero",
+        " **if** the `ZERO` bit of the flag register is set",
+    ),
+    (
+        "condition.NotZero",
+        " **if** the `ZERO` bit of the flag register is not set",
+    ),
+    (
+        "condition.Carry",
+        " **if** the `CARRY` bit of the flag register is set",
+    ),
+    (
+        "condition.NotCarry",
+        " **if** the `CARRY` bit of the flag register is not set",
+    ),
+    (
+        "condition.Greater",
+        " **if** the `GREATER` bit of the flag register is set",
+    ),
+    (
+        "condition.Lesser",
+        " **if** the `LESSER` bit of the flag register is set",
+    ),
+    (
+        "condition.EqualOrGreater",
+        " **if** either the `GREATER` or the `EQUAL` bit of the flag register is set",
+    ),
+    (
+        "condition.EqualOrLesser",
+        " **if** either the `LESSER` or the `EQUAL` bit of the flag register is set",
+    ),
+    (
+        "condition.Overflow",
+        " **if** the `ARITHMETIC_OVERFLOW` bit of the flag register is set",
+    ),
+    (
+        "condition.NotOverflow",
+        " **if** the `ARITHMETIC_OVERFLOW` bit of the flag register is not set",
+    ),
+    (
+        "condition.DivByZero",
+        " **if** the `DIV_BY_ZERO` bit of the flag register is set",
+    ),
+    (
+        "condition.Negative",
+        " **if** the `NEGATIVE` bit of the flag register is set",
+    ),
+    (
+        "description.RTS",
+        "Sets the *PC* to the last value saved on the *stack* plus 1.",
+    ),
+    (
+        "description.RTI",
+        "Sets the *PC* to the last value saved on the *stack*.",
+    ),
+    (
+        "description.PUSH",
+        "Saves the contents of a register or of the *flag register* on the *stack*.",
+    ),
+    (
+        "operation.PUSH",
+        "MEM(`SP`) ← `Rx`\n`SP` ← `SP` - 1 or\nMEM(`SP`) ← `FR`\n`SP` ← `SP` - 1",
+    ),
+    (
+        "description.POP",
+        "Restores the contents of a register or of the *flag register* from the *stack*.",
+    ),
+    (
+        "operation.POP",
+        "`SP` ← `SP` + 1\n`Rx` ← MEM(`SP`) or\n`SP` ← `SP` + 1\n`FR` ← MEM(`SP`)",
+    ),
+    ("description.NOP", "No operation. Only consumes time."),
+    ("operation.NOP", "None"),
+    ("description.HALT", "Stops the execution of the program."),
+    ("operation.HALT", "Halts the processor"),
+    (
+        "description.CLEARC",
+        "Clears the `CARRY` bit of the *flag register*.",
+    ),
+    (
+        "description.SETC",
+        "Sets the `CARRY` bit of the *flag register*.",
+    ),
+    (
+        "description.BREAKP",
+        "Triggers a *breakpoint* in the code, forcing the simulator into *debug* mode.",
+    ),
+    ("operation.BREAKP", "No logical operation in the processor"),
+];
+
+/// Retorna a mensagem `key` do catálogo no idioma `locale`, trocando cada `{nome}` do modelo
+/// pelo argumento de mesmo nome em `args`.
+pub(crate) fn message(locale: Locale, key: Key, args: &[(&str, &dyn Display)]) -> String {
+    let (_, portuguese, english) = MESSAGES
+        .iter()
+        .find(|(k, ..)| *k == key)
+        .expect("as chaves são geradas junto do catálogo");
+
+    let template = match locale {
+        Locale::Portuguese => portuguese,
+        Locale::English => english,
+    };
+
+    let mut text = String::new();
+    let mut rest = *template;
+
+    while let Some((before, after)) = rest.split_once('{') {
+        let Some((name, after)) = after.split_once('}') else {
+            break;
+        };
+
+        text += before;
+        match args.iter().find(|(n, _)| *n == name) {
+            Some((_, value)) => text += &value.to_string(),
+            None => text += &format!("{{{name}}}"),
+        }
+        rest = after;
+    }
+
+    text + rest
+}
+
+/// Retorna a tradução para o inglês `key` da documentação das instruções.
+pub(crate) fn documentation(key: &str) -> Option<&'static str> {
+    DOCUMENTATION
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, text)| *text)
+}
+
+/// Junta os mnemônicos das instruções, separados por vírgula.
+pub(crate) fn join(instructions: &[Instruction]) -> String {
+    let names: Vec<String> = instructions.iter().map(|i| i.to_string()).collect();
+    names.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EncodeError, InvalidInstruction, Register, Word};
+
+    /// Retorna os nomes dos argumentos do modelo, em ordem alfabética.
+    fn placeholders(template: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!("EN".parse(), Ok(Locale::English));
+        assert_eq!("pt_PT".parse(), Ok(Locale::Portuguese));
+        assert!("fr".parse::<Locale>().is_err());
+        assert_eq!(Locale::default(), Locale::Portuguese);
+    }
+
+    #[test]
+    fn test_catalog() {
+        for (i, (key, portuguese, english)) in MESSAGES.iter().enumerate() {
+            assert!(
+                MESSAGES[..i].iter().all(|(k, ..)| k.0 != key.0),
+                "chave duplicada: {}",
+                key.0
+            );
+            assert_eq!(placeholders(portuguese), placeholders(english), "{}", key.0);
+
+            // Sem argumentos, a mensagem é o próprio modelo.
+            assert_eq!(message(Locale::Portuguese, *key, &[]), *portuguese);
+            assert_eq!(message(Locale::English, *key, &[]), *english);
+        }
+
+        for (i, (key, _)) in DOCUMENTATION.iter().enumerate() {
+            assert!(
+                DOCUMENTATION[..i].iter().all(|(k, _)| k != key),
+                "chave duplicada: {key}"
+            );
+        }
+
+        assert_eq!(
+            message(
+                Locale::English,
+                key::ENCODE_SHIFT_OUT_OF_RANGE,
+                &[("n", &16)]
+            ),
+            "Shift out of range: 16"
+        );
+    }
+
+    #[test]
+    fn test_localized_errors() {
+        let err = InvalidInstruction::new(Word(0xffff)).at(Word(2));
+        assert_eq!(
+            err.localize(Locale::English),
+            "Invalid instruction: 0xffff (111111_111_111_111_1) at address 0x0002; nearest: OUTPUT"
+        );
+
+        let err = EncodeError::RegisterOutOfRange {
+            register: Register::SP,
+        };
+        assert_eq!(err.localize(Locale::Portuguese), err.to_string());
+        assert_eq!(
+            err.localize(Locale::English),
+            "Register SP cannot be encoded in an instruction"
+        );
+
+        let errors = crate::assemble("JMP fim").unwrap_err();
+        assert_eq!(
+            errors.errors()[0].localize(Locale::English),
+            "Line 1, column 5: Undefined label: fim"
+        );
+        assert_eq!(
+            errors.localize(Locale::English),
+            "Line 1, column 5: Undefined label: fim\n1 | JMP fim\n  |     ^^^"
+        );
+
+        let err = "ADDD".parse::<Instruction>().unwrap_err();
+        assert!(err
+            .localize(Locale::English)
+            .starts_with("Unknown mnemonic: ADDD (did you mean ADD"));
+    }
+
+    #[test]
+    fn test_documentation_covers_the_same_instructions() {
+        for instruction in Instruction::iter() {
+            assert_eq!(
+                instruction.description().is_some(),
+                instruction.localized_description(Locale::English).is_some(),
+                "{instruction}"
+            );
+            assert_eq!(
+                instruction.operation().is_some(),
+                instruction.localized_operation(Locale::English).is_some(),
+                "{instruction}"
+            );
+        }
+
+        for (key, _) in DOCUMENTATION {
+            let (section, name) = key.split_once('.').unwrap();
+            assert!(
+                name.parse::<Instruction>().is_ok()
+                    || matches!(name, "jump" | "call")
+                    || section == "condition",
+                "{key}"
+            );
+        }
+
+        assert_eq!(
+            Instruction::CNZ.localized_description(Locale::English),
+            Some(
+                "Saves the current value of the *PC* on the *stack* and jumps to the address of \
+                 the given procedure **if** the `ZERO` bit of the flag register is not set."
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            Instruction::MOV
+                .localized_operation(Locale::English)
+                .unwrap(),
+            "`Rx` ← `Ry` or\n`Rx` ← `SP` or\n`SP` ← `Rx`"
+        );
+    }
+}
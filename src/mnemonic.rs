@@ -0,0 +1,158 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::locale::{join, key, message};
+use crate::{Instruction, Locale, Localize};
+
+/// Distância máxima de edição para que um mnemônico seja sugerido.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Quantidade máxima de sugestões retornadas.
+const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub struct ParseInstructionError {
+    pub mnemonic: String,
+    /// Instruções cujos mnemônicos são parecidos com `mnemonic`, da mais para a menos parecida.
+    pub suggestions: Vec<Instruction>,
+}
+
+impl Localize for ParseInstructionError {
+    fn localize(&self, locale: Locale) -> String {
+        let mut text = message(
+            locale,
+            key::UNKNOWN_MNEMONIC,
+            &[("mnemonic", &self.mnemonic)],
+        );
+
+        if !self.suggestions.is_empty() {
+            let suggestions = join(&self.suggestions);
+            text += &message(
+                locale,
+                key::UNKNOWN_MNEMONIC_SUGGESTIONS,
+                &[("suggestions", &suggestions)],
+            );
+        }
+
+        text
+    }
+}
+
+/// Calcula a distância de Levenshtein entre `a` e `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + (ca != cb) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+impl FromStr for Instruction {
+    type Err = ParseInstructionError;
+
+    /// Converte um mnemônico, sem diferenciar maiúsculas de minúsculas, na [`Instruction`]
+    /// correspondente.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// assert_eq!(Instruction::ADDC, "addc".parse().unwrap());
+    ///
+    /// let err = "ADX".parse::<Instruction>().unwrap_err();
+    /// assert!(err.suggestions.contains(&Instruction::ADD));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mnemonic = s.trim().to_ascii_uppercase();
+
+        if let Some(instruction) = Instruction::iter().find(|i| i.mnemonic() == mnemonic) {
+            return Ok(instruction);
+        }
+
+        let mut candidates: Vec<(usize, Instruction)> = Instruction::iter()
+            .map(|instruction| {
+                (
+                    edit_distance(&mnemonic, instruction.mnemonic()),
+                    instruction,
+                )
+            })
+            .filter(|&(distance, _)| distance <= MAX_SUGGESTION_DISTANCE)
+            .collect();
+        candidates.sort_by_key(|&(distance, _)| distance);
+
+        Err(ParseInstructionError {
+            mnemonic: s.to_string(),
+            suggestions: candidates
+                .into_iter()
+                .take(MAX_SUGGESTIONS)
+                .map(|(_, instruction)| instruction)
+                .collect(),
+        })
+    }
+}
+
+impl TryFrom<&str> for Instruction {
+    type Error = ParseInstructionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_every_mnemonic() {
+        for instruction in Instruction::iter() {
+            let name = instruction.mnemonic();
+            assert_eq!(name.parse(), Ok(instruction));
+            assert_eq!(name.to_lowercase().parse(), Ok(instruction));
+            assert_eq!(instruction.to_string().parse(), Ok(instruction));
+        }
+    }
+
+    #[test]
+    fn test_parse_suggestions() {
+        let err = Instruction::try_from("shiftl").unwrap_err();
+        assert_eq!(err.mnemonic, "shiftl");
+        assert_eq!(
+            err.suggestions,
+            vec![
+                Instruction::SHIFTL0,
+                Instruction::SHIFTL1,
+                Instruction::SHIFTR0
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "Mnemônico desconhecido: shiftl (você quis dizer SHIFTL0, SHIFTL1, SHIFTR0?)"
+        );
+
+        let err = Instruction::try_from("FOOBAR").unwrap_err();
+        assert!(err.suggestions.is_empty());
+        assert_eq!(err.to_string(), "Mnemônico desconhecido: FOOBAR");
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("ADD", "ADD"), 0);
+        assert_eq!(edit_distance("ADX", "ADD"), 1);
+        assert_eq!(edit_distance("AD", "ADDC"), 2);
+        assert_eq!(edit_distance("", "NOP"), 3);
+    }
+}
@@ -0,0 +1,963 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+
+use crate::assembler::{
+    constant_definition, split_label, split_unquoted, split_word, string_literal, strip_comment,
+    Symbol, Symbols,
+};
+use crate::diagnostic::Part;
+use crate::expression::{is_identifier, Expr};
+use crate::{
+    assemble_indexed, AssembleError, AssembleErrorKind, AssembleErrors, Instruction, Span, Word,
+    MEMORY_SIZE,
+};
+
+/// Profundidade máxima de macros expandidas dentro de outras macros.
+const MAX_DEPTH: usize = 64;
+
+/// Quantidade máxima de linhas geradas por macros em um pré-processamento. Macros que usam
+/// outras macros várias vezes geram uma quantidade de linhas exponencial na profundidade, e o
+/// limite, bem maior que as [`MEMORY_SIZE`] palavras de um programa, impede que esgotem a memória.
+const MAX_EXPANDED_LINES: usize = 2 * MEMORY_SIZE;
+
+/// Uma linha gerada pelo [`Preprocessor`], com o arquivo e a linha (começando em 1) de onde foi
+/// lida. Linhas geradas por uma macro apontam para o corpo da macro, e guardam em `call` o uso
+/// da macro.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLine {
+    pub file: String,
+    pub line: usize,
+    pub text: String,
+    pub call: Option<Box<MacroCall>>,
+}
+
+/// O uso de uma macro que gerou uma [`SourceLine`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroCall {
+    /// Linha em que a macro foi usada.
+    pub line: SourceLine,
+    /// *Bytes* do nome da macro em `line`.
+    pub name: Range<usize>,
+    /// Argumentos copiados para a linha gerada.
+    pub arguments: Vec<MacroArgument>,
+}
+
+/// Um argumento de macro copiado para uma linha gerada.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroArgument {
+    /// *Bytes* que o argumento ocupa na linha gerada.
+    pub copy: Range<usize>,
+    /// *Bytes* em que o argumento foi escrito na linha em que a macro foi usada.
+    pub written: Range<usize>,
+}
+
+impl SourceLine {
+    /// Retorna o trecho da linha que ocupa os *bytes* `range`. Em linhas geradas por macros, um
+    /// trecho dentro de um argumento aponta para o argumento no uso da macro, e os demais
+    /// guardam o uso da macro em [`Span::expansion`].
+    pub(crate) fn span(&self, range: Range<usize>) -> Span {
+        let Some(call) = &self.call else {
+            return Span::new(Some(&self.file), self.line, &self.text, range);
+        };
+
+        // Os argumentos são copiados sem alterações, então a posição dentro deles se mantém.
+        let argument = call
+            .arguments
+            .iter()
+            .find(|argument| argument.copy.start <= range.start && range.end <= argument.copy.end);
+
+        if let Some(MacroArgument { copy, written }) = argument {
+            let start = written.start + range.start - copy.start;
+            return call.line.span(start..start + range.len());
+        }
+
+        Span {
+            expansion: Some(Box::new(call.line.span(call.name.clone()))),
+            ..Span::new(Some(&self.file), self.line, &self.text, range)
+        }
+    }
+}
+
+/// Separa o código-fonte `source`, lido do arquivo `file`, em linhas.
+fn source_lines(file: &str, source: &str) -> Vec<SourceLine> {
+    source
+        .lines()
+        .enumerate()
+        .map(|(i, text)| SourceLine {
+            file: file.to_string(),
+            line: i + 1,
+            text: text.to_string(),
+            call: None,
+        })
+        .collect()
+}
+
+/// Cria um erro na parte `part` da linha `line`.
+fn error(line: &SourceLine, part: Part, kind: AssembleErrorKind) -> AssembleError {
+    AssembleError {
+        kind,
+        span: line.span(part.range()),
+    }
+}
+
+/// Remove os componentes `.` e `..` do caminho, sem acessar o sistema de arquivos.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+/// Substitui, em `text`, os identificadores fora de literais e de comentários pelos valores
+/// dados em `names`, acompanhados, nos argumentos de uma macro, dos *bytes* em que foram escritos
+/// no uso da macro. Retorna o texto e os argumentos copiados para ele.
+fn substitute(
+    text: &str,
+    names: &HashMap<&str, (String, Option<Range<usize>>)>,
+) -> (String, Vec<MacroArgument>) {
+    let mut result = String::new();
+    let mut arguments = vec![];
+    let mut quote = None;
+    let mut escaped = false;
+    let mut i = 0;
+
+    while let Some(c) = text[i..].chars().next() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == ';' => {
+                result += &text[i..];
+                break;
+            }
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c.is_ascii_alphanumeric() || c == '_' => {
+                let len = text[i..]
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                    .unwrap_or(text.len() - i);
+                let word = &text[i..i + len];
+
+                match names.get(word) {
+                    Some((value, written)) if !c.is_ascii_digit() => {
+                        if let Some(written) = written {
+                            let start = result.len();
+                            arguments.push(MacroArgument {
+                                copy: start..start + value.len(),
+                                written: written.clone(),
+                            });
+                        }
+
+                        result += value;
+                    }
+                    _ => result += word,
+                }
+
+                i += len;
+                continue;
+            }
+            None => {}
+        }
+
+        result.push(c);
+        i += c.len_utf8();
+    }
+
+    (result, arguments)
+}
+
+/// Uma macro definida com `macro NOME p1, p2` ... `endm`.
+#[derive(Debug, Clone, Default)]
+struct Macro {
+    params: Vec<String>,
+    /// Rótulos definidos no corpo, que recebem nomes diferentes em cada expansão.
+    labels: Vec<String>,
+    body: Vec<SourceLine>,
+}
+
+/// Um bloco `if` ainda não terminado por `endif`.
+struct Conditional {
+    /// Linha do `if`.
+    line: SourceLine,
+    /// Se o bloco que contém o `if` é montado.
+    parent: bool,
+    /// Valor da condição do `if`.
+    taken: bool,
+    /// Se o `else` já foi lido.
+    otherwise: bool,
+}
+
+impl Conditional {
+    /// Indica se as linhas atuais do bloco são montadas.
+    fn active(&self) -> bool {
+        self.parent && self.taken != self.otherwise
+    }
+}
+
+/// Pré-processador do *assembly* do Processador ICMC, executado antes do [`assemble_lines`](crate::assemble_lines).
+///
+/// São tratadas as diretivas:
+/// * `include "arquivo.asm"`: insere as linhas de outro arquivo, procurado a partir do
+///   diretório do arquivo atual;
+/// * `macro NOME p1, p2` ... `endm`: define uma macro, usada como uma instrução
+///   (`NOME a, b`). Os parâmetros são substituídos pelos argumentos, e os rótulos definidos no
+///   corpo recebem um nome diferente em cada expansão;
+/// * `if condição`, `else` e `endif`: montam as linhas apenas se a condição, que só pode usar
+///   constantes definidas antes, for diferente de 0.
+///
+/// Os arquivos incluídos são lidos do sistema de arquivos, exceto os registrados com
+/// [`Preprocessor::with_file`]. Os erros apontam para o arquivo e a linha originais, mesmo em
+/// linhas geradas por macros, e o pré-processamento continua após cada erro. Um erro em um
+/// argumento de macro aponta para o argumento no uso da macro; os demais erros em linhas geradas
+/// por macros também indicam o uso da macro em [`Span::expansion`].
+///
+/// ## Exemplo
+///
+/// ```
+/// use isa::*;
+///
+/// let preprocessor = Preprocessor::new().with_file(
+///     "lib/video.asm",
+///     "
+///     AMARELO equ 2816
+///
+///     macro print c, pos
+///         LOADN R0, #c
+///         LOADN R1, #pos
+///         OUTCHAR R0, R1
+///     endm
+///     ",
+/// );
+///
+/// let program = preprocessor
+///     .assemble(
+///         "main.asm",
+///         "
+///         include \"lib/video.asm\"
+///
+///         if AMARELO > 0
+///             print 'A' + AMARELO, 40
+///         else
+///             print 'A', 40
+///         endif
+///         HALT
+///         ",
+///     )
+///     .unwrap();
+///
+/// assert_eq!(Word('A' as u16 + 2816), program[1]);
+/// assert_eq!(Word(40), program[3]);
+///
+/// let errors = preprocessor.assemble("main.asm", "include \"lib/video.asm\"\nprint R1, 0");
+/// let error = errors.unwrap_err().into_iter().next().unwrap();
+/// assert_eq!(Some("main.asm"), error.span.file.as_deref());
+/// assert_eq!((2, 7), (error.span.line, error.span.column));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Preprocessor {
+    files: HashMap<PathBuf, String>,
+}
+
+impl Preprocessor {
+    /// Cria um pré-processador que lê os arquivos incluídos do sistema de arquivos.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra o conteúdo do arquivo `path`, usado em vez do arquivo no sistema de arquivos.
+    pub fn with_file(mut self, path: impl AsRef<Path>, source: impl Into<String>) -> Self {
+        self.files.insert(normalize(path.as_ref()), source.into());
+        self
+    }
+
+    fn read(&self, path: &Path) -> std::io::Result<String> {
+        match self.files.get(path) {
+            Some(source) => Ok(source.clone()),
+            None => std::fs::read_to_string(path),
+        }
+    }
+
+    /// Pré-processa o código-fonte `source`, lido do arquivo `file`.
+    pub fn preprocess(&self, file: &str, source: &str) -> Result<Vec<SourceLine>, AssembleErrors> {
+        let (lines, errors) = self.expand(file, source);
+
+        match errors.is_empty() {
+            true => Ok(lines),
+            false => Err(AssembleErrors::from_lines(errors)),
+        }
+    }
+
+    /// Pré-processa e monta o código-fonte `source`, lido do arquivo `file`. Mesmo com erros no
+    /// pré-processamento, as linhas geradas são montadas, e os erros das duas etapas são
+    /// retornados juntos, na ordem das linhas.
+    pub fn assemble(&self, file: &str, source: &str) -> Result<Vec<Word>, AssembleErrors> {
+        let (lines, mut errors) = self.expand(file, source);
+
+        match assemble_indexed(&lines) {
+            Ok(program) if errors.is_empty() => return Ok(program),
+            Ok(_) => {}
+            Err(assembly) => errors.extend(assembly),
+        }
+
+        Err(AssembleErrors::from_lines(errors))
+    }
+
+    /// Pré-processa o código-fonte `source`, retornando as linhas geradas e os erros, cada um
+    /// junto do índice da linha gerada antes da qual foi encontrado.
+    fn expand(&self, file: &str, source: &str) -> (Vec<SourceLine>, Vec<(usize, AssembleError)>) {
+        let mut expansion = Expansion {
+            preprocessor: self,
+            symbols: Symbols::default(),
+            macros: HashMap::new(),
+            includes: vec![normalize(Path::new(file))],
+            calls: vec![],
+            expansions: 0,
+            expanded_lines: 0,
+            output: vec![],
+            errors: vec![],
+        };
+
+        expansion.process(source_lines(file, source));
+        (expansion.output, expansion.errors)
+    }
+}
+
+/// Estado do pré-processamento de um arquivo.
+struct Expansion<'a> {
+    preprocessor: &'a Preprocessor,
+    /// Constantes definidas até a linha atual, usadas nas condições dos `if`.
+    symbols: Symbols,
+    macros: HashMap<String, Macro>,
+    /// Arquivos sendo incluídos, para detectar inclusões recursivas.
+    includes: Vec<PathBuf>,
+    /// Macros sendo expandidas, para detectar expansões recursivas.
+    calls: Vec<String>,
+    /// Quantidade de macros expandidas, usada para renomear os rótulos.
+    expansions: usize,
+    /// Quantidade de linhas geradas por macros, limitada por [`MAX_EXPANDED_LINES`].
+    expanded_lines: usize,
+    output: Vec<SourceLine>,
+    /// Erros encontrados, junto da quantidade de linhas geradas antes de cada um.
+    errors: Vec<(usize, AssembleError)>,
+}
+
+impl Expansion<'_> {
+    fn report(&mut self, error: AssembleError) {
+        self.errors.push((self.output.len(), error));
+    }
+
+    /// Pré-processa as linhas `lines`, de um arquivo ou do corpo de uma macro expandida.
+    fn process(&mut self, lines: Vec<SourceLine>) {
+        let mut conditionals: Vec<Conditional> = vec![];
+        // Uma macro com cabeçalho inválido é lida até o `endm`, mas não é definida.
+        let mut definition: Option<(Option<String>, Macro, SourceLine)> = None;
+
+        for line in lines {
+            let (word, operands) = split_word(strip_comment(Part::line(&line.text)));
+            let directive = word.to_ascii_lowercase();
+            let unexpected = || {
+                let directive = directive.clone();
+                error(
+                    &line,
+                    word,
+                    AssembleErrorKind::UnexpectedDirective { directive },
+                )
+            };
+
+            if let Some((_, definition_macro, _)) = &mut definition {
+                match directive.as_str() {
+                    "endm" => {
+                        let (name, mut definition_macro, _) = definition.take().unwrap();
+                        definition_macro.labels = local_labels(&definition_macro.body);
+
+                        if let Some(name) = name {
+                            self.macros.insert(name, definition_macro);
+                        }
+                    }
+                    "macro" => self.report(unexpected()),
+                    _ => definition_macro.body.push(line),
+                }
+
+                continue;
+            }
+
+            let active = match conditionals.last() {
+                Some(conditional) => conditional.active(),
+                None => true,
+            };
+
+            match directive.as_str() {
+                "if" => {
+                    let taken = active
+                        && self.condition(&operands).unwrap_or_else(|kind| {
+                            self.report(error(&line, operands, kind));
+                            false
+                        });
+
+                    conditionals.push(Conditional {
+                        line: line.clone(),
+                        parent: active,
+                        taken,
+                        otherwise: false,
+                    });
+                }
+                "else" => match conditionals.last_mut() {
+                    Some(conditional) if !conditional.otherwise => conditional.otherwise = true,
+                    _ => self.report(unexpected()),
+                },
+                "endif" => {
+                    if conditionals.pop().is_none() {
+                        self.report(unexpected());
+                    }
+                }
+                _ if !active => {}
+                "macro" => {
+                    let (name, definition_macro) = match self.definition(operands) {
+                        Ok((name, definition_macro)) => (Some(name), definition_macro),
+                        Err((kind, part)) => {
+                            self.report(error(&line, part, kind));
+                            (None, Macro::default())
+                        }
+                    };
+
+                    definition = Some((name, definition_macro, line.clone()));
+                }
+                "endm" => self.report(unexpected()),
+                "include" => self.include(&line, operands),
+                _ => self.statement(line),
+            }
+        }
+
+        let mut unterminated = |line: &SourceLine, directive: &str| {
+            let word = split_word(strip_comment(Part::line(&line.text))).0;
+            let directive = directive.to_string();
+            self.report(error(
+                line,
+                word,
+                AssembleErrorKind::UnterminatedBlock { directive },
+            ));
+        };
+
+        if let Some((_, _, line)) = definition {
+            unterminated(&line, "macro");
+        }
+
+        for conditional in conditionals {
+            unterminated(&conditional.line, "if");
+        }
+    }
+
+    /// Avalia a condição de um `if`.
+    fn condition(&self, text: &str) -> Result<bool, AssembleErrorKind> {
+        Ok(self.symbols.evaluate(&Expr::parse(text)?, false)? != 0)
+    }
+
+    /// Lê o cabeçalho `NOME p1, p2` de uma macro. Os erros são retornados junto do trecho
+    /// inválido.
+    fn definition<'a>(
+        &self,
+        operands: Part<'a>,
+    ) -> Result<(String, Macro), (AssembleErrorKind, Part<'a>)> {
+        let (name, params) = split_word(operands);
+
+        if !is_identifier(&name) || name.parse::<Instruction>().is_ok() {
+            let label = name.to_string();
+            return Err((AssembleErrorKind::InvalidLabel { label }, name));
+        }
+
+        if self.macros.contains_key(name.as_str()) {
+            let kind = AssembleErrorKind::DuplicateMacro {
+                name: name.to_string(),
+            };
+            return Err((kind, name));
+        }
+
+        let params = match params.is_empty() {
+            true => vec![],
+            false => split_unquoted(params, ',')
+                .into_iter()
+                .map(|param| match param.trim() {
+                    param if is_identifier(&param) => Ok(param.to_string()),
+                    param => {
+                        let label = param.to_string();
+                        Err((AssembleErrorKind::InvalidLabel { label }, param))
+                    }
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        let definition = Macro {
+            params,
+            ..Macro::default()
+        };
+
+        Ok((name.to_string(), definition))
+    }
+
+    /// Insere as linhas do arquivo `include "arquivo.asm"`.
+    fn include(&mut self, line: &SourceLine, operands: Part) {
+        let Some(name) = string_literal(operands) else {
+            let kind = AssembleErrorKind::InvalidDirective {
+                directive: "include".to_string(),
+                operands: operands.to_string(),
+            };
+            let part = match operands.is_empty() {
+                true => split_word(strip_comment(Part::line(&line.text))).0,
+                false => operands,
+            };
+            self.report(error(line, part, kind));
+            return;
+        };
+
+        let directory = Path::new(&line.file).parent().unwrap_or(Path::new(""));
+        let path = normalize(&directory.join(name.as_str()));
+        let display = path.display().to_string();
+
+        if self.includes.contains(&path) {
+            let kind = AssembleErrorKind::RecursiveInclude { path: display };
+            self.report(error(line, operands, kind));
+            return;
+        }
+
+        let source = match self.preprocessor.read(&path) {
+            Ok(source) => source,
+            Err(e) => {
+                let kind = AssembleErrorKind::Include {
+                    path: display,
+                    message: e.to_string(),
+                };
+                self.report(error(line, operands, kind));
+                return;
+            }
+        };
+
+        self.includes.push(path);
+        self.process(source_lines(&display, &source));
+        self.includes.pop();
+    }
+
+    /// Copia uma linha comum para a saída, expandindo-a se for o uso de uma macro.
+    fn statement(&mut self, line: SourceLine) {
+        let (label, rest) = split_label(strip_comment(Part::line(&line.text)));
+
+        if let Some((name, value)) = constant_definition(label, rest) {
+            if let Ok(expr) = Expr::parse(&value) {
+                if let Err(kind) = self.symbols.insert(&name, Symbol::Constant(expr)) {
+                    self.report(error(&line, name, kind));
+                }
+            }
+
+            self.output.push(line);
+            return;
+        }
+
+        // Rótulos são registrados apenas para que o seu uso em um `if` seja recusado.
+        if let Some(label) = label {
+            if let Err(kind) = self.symbols.insert(&label, Symbol::Label(Word::ZERO)) {
+                self.report(error(&line, label, kind));
+            }
+        }
+
+        let (name, arguments) = split_word(rest);
+
+        let Some(definition) = self.macros.get(name.as_str()).cloned() else {
+            self.output.push(line);
+            return;
+        };
+
+        if self.calls.iter().any(|call| *call == *name) || self.calls.len() >= MAX_DEPTH {
+            let kind = AssembleErrorKind::RecursiveMacro {
+                name: name.to_string(),
+            };
+            self.report(error(&line, name, kind));
+            return;
+        }
+
+        let arguments: Vec<Part> = match arguments.is_empty() {
+            true => vec![],
+            false => split_unquoted(arguments, ',')
+                .into_iter()
+                .map(|argument| argument.trim())
+                .collect(),
+        };
+
+        if arguments.len() != definition.params.len() {
+            let kind = AssembleErrorKind::MacroArgumentCount {
+                name: name.to_string(),
+                expected: definition.params.len(),
+                found: arguments.len(),
+            };
+            self.report(error(&line, rest, kind));
+            return;
+        }
+
+        // Após o primeiro erro, as demais expansões são ignoradas, sem repetir o erro.
+        if self.expanded_lines + definition.body.len() > MAX_EXPANDED_LINES {
+            if self.expanded_lines <= MAX_EXPANDED_LINES {
+                let kind = AssembleErrorKind::MacroExpansionTooLarge {
+                    name: name.to_string(),
+                    lines: MAX_EXPANDED_LINES,
+                };
+                self.report(error(&line, name, kind));
+                self.expanded_lines = MAX_EXPANDED_LINES + 1;
+            }
+
+            return;
+        }
+
+        self.expanded_lines += definition.body.len();
+
+        // O rótulo é mantido na mesma posição, para que os erros apontem para o texto original.
+        if label.is_some() {
+            self.output.push(SourceLine {
+                text: line.text[..rest.range().start].to_string(),
+                ..line.clone()
+            });
+        }
+
+        self.expansions += 1;
+
+        let mut names: HashMap<&str, (String, Option<Range<usize>>)> = definition
+            .params
+            .iter()
+            .map(String::as_str)
+            .zip(
+                arguments
+                    .iter()
+                    .map(|argument| (argument.to_string(), Some(argument.range()))),
+            )
+            .collect();
+
+        for label in &definition.labels {
+            let renamed = format!("__{name}_{}_{label}", self.expansions);
+            names.insert(label, (renamed, None));
+        }
+
+        let body = definition
+            .body
+            .iter()
+            .map(|body_line| {
+                let (text, arguments) = substitute(&body_line.text, &names);
+
+                SourceLine {
+                    text,
+                    call: Some(Box::new(MacroCall {
+                        line: line.clone(),
+                        name: name.range(),
+                        arguments,
+                    })),
+                    ..body_line.clone()
+                }
+            })
+            .collect();
+
+        self.calls.push(name.to_string());
+        self.process(body);
+        self.calls.pop();
+    }
+}
+
+/// Retorna os rótulos definidos nas linhas `body` do corpo de uma macro.
+fn local_labels(body: &[SourceLine]) -> Vec<String> {
+    body.iter()
+        .filter_map(|line| {
+            let (label, rest) = split_label(strip_comment(Part::line(&line.text)));
+            let label = label.filter(|label| is_identifier(label))?;
+            constant_definition(Some(label), rest)
+                .is_none()
+                .then(|| label.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assemble_lines, Locale};
+
+    fn preprocess(source: &str) -> Result<Vec<String>, AssembleErrors> {
+        let lines = Preprocessor::new().preprocess("main.asm", source)?;
+
+        Ok(lines
+            .into_iter()
+            .map(|line| line.text.trim().to_string())
+            .filter(|text| !text.is_empty())
+            .collect())
+    }
+
+    fn error_kind(source: &str) -> AssembleErrorKind {
+        preprocess(source)
+            .unwrap_err()
+            .into_iter()
+            .next()
+            .unwrap()
+            .kind
+    }
+
+    #[test]
+    fn test_macro_local_labels() {
+        let source = "
+            macro wait n
+                LOADN R0, #n    ; n vezes
+            loop:
+                DEC R0
+                JNZ loop
+            endm
+
+            wait 3
+            fim: wait 0x10
+        ";
+
+        assert_eq!(
+            preprocess(source).unwrap(),
+            vec![
+                "LOADN R0, #3    ; n vezes",
+                "__wait_1_loop:",
+                "DEC R0",
+                "JNZ __wait_1_loop",
+                "fim:",
+                "LOADN R0, #0x10    ; n vezes",
+                "__wait_2_loop:",
+                "DEC R0",
+                "JNZ __wait_2_loop",
+            ]
+        );
+
+        let program = Preprocessor::new().assemble("main.asm", source).unwrap();
+        assert_eq!(program[4], Word(2));
+        assert_eq!(program[9], Word(7));
+    }
+
+    #[test]
+    fn test_conditionals() {
+        let source = "
+            DEBUG equ 0
+            if DEBUG
+                if INDEFINIDA
+                    NOP
+                endif
+                BREAKP
+            else
+                if DEBUG == 0 & 1
+                    HALT
+                endif
+            endif
+        ";
+
+        assert_eq!(preprocess(source).unwrap(), vec!["DEBUG equ 0", "HALT"]);
+    }
+
+    #[test]
+    fn test_include_locations() {
+        let preprocessor = Preprocessor::new()
+            .with_file("src/main.asm", "include \"../lib/util.asm\"\nNOP")
+            .with_file("lib/util.asm", "macro erro\n  LOADN R0, 5\nendm");
+
+        let lines = preprocessor
+            .preprocess("src/app.asm", "include \"main.asm\"\nerro")
+            .unwrap();
+        let locations: Vec<(&str, usize)> = lines
+            .iter()
+            .map(|line| (line.file.as_str(), line.line))
+            .collect();
+        assert_eq!(locations, vec![("src/main.asm", 2), ("lib/util.asm", 2)]);
+
+        let errors = assemble_lines(&lines).unwrap_err();
+        assert_eq!(
+            errors.to_string(),
+            "lib/util.asm, linha 2, coluna 13: Operando inválido para a instrução LOADN: 5\n\
+             2 |   LOADN R0, 5\n  \
+               |             ^\n\
+             Na expansão da macro usada em src/app.asm, linha 2, coluna 1:\n\
+             2 | erro\n  \
+               | ^^^^"
+        );
+    }
+
+    #[test]
+    fn test_macro_call_locations() {
+        let source = "
+            macro carrega r, valor
+                LOADN r, #valor
+            endm
+            macro dobra r, valor
+            x:  carrega r, valor
+                JMP y
+            endm
+            inicio: dobra R1, 3 + fim
+        ";
+        let lines = Preprocessor::new().preprocess("main.asm", source).unwrap();
+        let errors = assemble_lines(&lines).unwrap_err();
+        let errors = errors.errors();
+        assert_eq!(errors.len(), 2);
+
+        // O erro no argumento aponta para o uso da macro, onde o argumento foi escrito.
+        let (span, text) = (&errors[0].span, source.lines().nth(8).unwrap());
+        assert_eq!(
+            errors[0].kind,
+            AssembleErrorKind::UndefinedLabel {
+                label: "fim".to_string()
+            }
+        );
+        assert_eq!((span.line, span.len), (9, 7));
+        assert_eq!(&text[span.column - 1..], "3 + fim");
+        assert_eq!(span.expansion, None);
+
+        // Os demais erros guardam o uso da macro.
+        let span = &errors[1].span;
+        assert_eq!((span.line, span.column, span.len), (7, 21, 1));
+        let expansion = span.expansion.as_deref().unwrap();
+        assert_eq!(
+            (expansion.line, expansion.column, expansion.len),
+            (9, 21, 5)
+        );
+        assert!(errors[1].to_json(Locale::English).starts_with(
+            "{\"file\":\"main.asm\",\"line\":7,\"column\":21,\"length\":1,\
+             \"expansion\":{\"file\":\"main.asm\",\"line\":9,\"column\":21,\"length\":5,\
+             \"expansion\":null}"
+        ));
+    }
+
+    #[test]
+    fn test_errors() {
+        let preprocessor = Preprocessor::new()
+            .with_file("a.asm", "include \"b.asm\"")
+            .with_file("b.asm", "include \"a.asm\"");
+
+        let errors = preprocessor
+            .preprocess("main.asm", "\ninclude \"a.asm\"")
+            .unwrap_err();
+        let recursive = &errors.errors()[0];
+        assert_eq!(
+            recursive.kind,
+            AssembleErrorKind::RecursiveInclude {
+                path: "a.asm".to_string()
+            }
+        );
+        assert_eq!(
+            (recursive.span.file.as_deref(), recursive.span.line),
+            (Some("b.asm"), 1)
+        );
+        assert!(matches!(
+            error_kind("include \"inexistente.asm\""),
+            AssembleErrorKind::Include { .. }
+        ));
+        assert_eq!(
+            error_kind("if 1\nNOP"),
+            AssembleErrorKind::UnterminatedBlock {
+                directive: "if".to_string()
+            }
+        );
+        assert_eq!(
+            error_kind("macro m\nNOP"),
+            AssembleErrorKind::UnterminatedBlock {
+                directive: "macro".to_string()
+            }
+        );
+        assert_eq!(
+            error_kind("if 1\nelse\nelse\nendif"),
+            AssembleErrorKind::UnexpectedDirective {
+                directive: "else".to_string()
+            }
+        );
+        assert_eq!(
+            error_kind("macro m a, b\nendm\nm 1"),
+            AssembleErrorKind::MacroArgumentCount {
+                name: "m".to_string(),
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(
+            error_kind("macro m\nm\nendm\nm"),
+            AssembleErrorKind::RecursiveMacro {
+                name: "m".to_string()
+            }
+        );
+        assert_eq!(
+            error_kind("macro m\nm\nm\nendm\nm"),
+            AssembleErrorKind::RecursiveMacro {
+                name: "m".to_string()
+            }
+        );
+        assert!(matches!(
+            error_kind("macro NOP\nendm"),
+            AssembleErrorKind::InvalidLabel { .. }
+        ));
+        assert_eq!(
+            error_kind("N equ 0\nN equ 1\nif N\nHALT\nendif"),
+            AssembleErrorKind::DuplicateLabel {
+                label: "N".to_string()
+            }
+        );
+        assert!(matches!(
+            error_kind("x: NOP\nif x\nendif"),
+            AssembleErrorKind::NotConstant { .. }
+        ));
+    }
+
+    #[test]
+    fn test_assemble_reports_every_error() {
+        let errors = Preprocessor::new()
+            .assemble("main.asm", "LOADN R1, 5\nendm\nJMP fim\nm 1")
+            .unwrap_err();
+        let found: Vec<(&str, usize)> = errors
+            .errors()
+            .iter()
+            .map(|error| (error.kind.name(), error.span.line))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                ("invalid_operand", 1),
+                ("unexpected_directive", 2),
+                ("undefined_label", 3),
+                ("unknown_mnemonic", 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expansion_limit() {
+        // Cada macro usa a anterior duas vezes, gerando 2^30 linhas.
+        let mut source = "macro m0\nNOP\nendm\n".to_string();
+        for i in 1..=30 {
+            source += &format!("macro m{i}\nm{0}\nm{0}\nendm\n", i - 1);
+        }
+        source += "m30";
+
+        let errors = Preprocessor::new()
+            .preprocess("main.asm", &source)
+            .unwrap_err();
+        assert_eq!(errors.errors().len(), 1);
+
+        let error = &errors.errors()[0];
+        assert_eq!(
+            error.kind,
+            AssembleErrorKind::MacroExpansionTooLarge {
+                name: "m0".to_string(),
+                lines: MAX_EXPANDED_LINES
+            }
+        );
+        assert!(error.span.expansion.is_some());
+
+        let source = "macro m\nNOP\nendm\n".to_string() + &"m\n".repeat(MAX_EXPANDED_LINES);
+        assert_eq!(preprocess(&source).unwrap().len(), MAX_EXPANDED_LINES);
+    }
+}
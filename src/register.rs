@@ -0,0 +1,191 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::locale::{key, message};
+use crate::{Locale, Localize};
+
+/// Quantidade de *bits* de uma palavra (e de um endereço) do Processador ICMC.
+pub const BITS_ADDRESS: usize = 16;
+
+/// Quantidade de palavras da memória do Processador ICMC.
+pub const MEMORY_SIZE: usize = 1 << 15;
+
+/// Quantidade de registradores de propósito geral (`R0` a `R7`).
+pub const GENERAL_REGISTERS: usize = 8;
+
+/// Quantidade de colunas da tela do Processador ICMC.
+pub const SCREEN_WIDTH: usize = 40;
+
+/// Quantidade de linhas da tela do Processador ICMC.
+pub const SCREEN_HEIGHT: usize = 30;
+
+/// Registradores presentes na Arquitetura do Processador ICMC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Register {
+    R0,
+    R1,
+    R2,
+    R3,
+    R4,
+    R5,
+    R6,
+    R7,
+    /// *Stack pointer*.
+    SP,
+    /// *Program counter*.
+    PC,
+    /// *Flag register*. Cada um de seus *bits* é indicado por um [`FlagIndex`].
+    FR,
+}
+
+impl Register {
+    /// Registradores de propósito geral, indexados pelo valor dos campos `Rx`, `Ry` e `Rz`.
+    pub const GENERAL: [Register; GENERAL_REGISTERS] = [
+        Register::R0,
+        Register::R1,
+        Register::R2,
+        Register::R3,
+        Register::R4,
+        Register::R5,
+        Register::R6,
+        Register::R7,
+    ];
+
+    /// Retorna o registrador de propósito geral de índice `index`.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// assert_eq!(Some(Register::R3), Register::from_index(3));
+    /// assert_eq!(None, Register::from_index(8));
+    /// ```
+    pub fn from_index(index: usize) -> Option<Register> {
+        Register::GENERAL.get(index).copied()
+    }
+
+    /// Retorna o índice do registrador, caso seja de propósito geral.
+    pub fn index(&self) -> Option<usize> {
+        Register::GENERAL.iter().position(|r| r == self)
+    }
+
+    /// Indica se o registrador é de propósito geral, isto é, se pode ser codificado nos campos
+    /// `Rx`, `Ry` e `Rz` de uma instrução.
+    pub fn is_general_purpose(&self) -> bool {
+        self.index().is_some()
+    }
+}
+
+impl std::fmt::Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub struct ParseRegisterError {
+    pub name: String,
+}
+
+impl Localize for ParseRegisterError {
+    fn localize(&self, locale: Locale) -> String {
+        message(locale, key::UNKNOWN_REGISTER, &[("name", &self.name)])
+    }
+}
+
+impl FromStr for Register {
+    type Err = ParseRegisterError;
+
+    /// Converte o nome de um registrador, sem diferenciar maiúsculas de minúsculas.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// assert_eq!(Register::R5, "r5".parse().unwrap());
+    /// assert_eq!(Register::FR, "FR".parse().unwrap());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let register = match s.trim().to_ascii_uppercase().as_str() {
+            "SP" => Register::SP,
+            "PC" => Register::PC,
+            "FR" => Register::FR,
+            name => name
+                .strip_prefix('R')
+                .filter(|index| index.len() == 1)
+                .and_then(|index| index.parse().ok())
+                .and_then(Register::from_index)
+                .ok_or_else(|| ParseRegisterError {
+                    name: s.to_string(),
+                })?,
+        };
+
+        Ok(register)
+    }
+}
+
+/// Índices dos *bits* do *flag register* ([`Register::FR`]).
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FlagIndex {
+    /// O primeiro operando da última comparação é maior que o segundo.
+    GREATER = 0,
+    /// O primeiro operando da última comparação é menor que o segundo.
+    LESSER = 1,
+    /// Os operandos da última comparação são iguais.
+    EQUAL = 2,
+    /// O resultado da última operação é zero.
+    ZERO = 3,
+    /// A última operação gerou *carry*.
+    CARRY = 4,
+    /// A última operação aritmética transbordou.
+    ARITHMETIC_OVERFLOW = 5,
+    /// A última operação foi uma divisão por zero.
+    DIV_BY_ZERO = 6,
+    /// A *stack* transbordou.
+    STACK_OVERFLOW = 7,
+    /// Foi retirado um valor da *stack* vazia.
+    STACK_UNDERFLOW = 8,
+    /// O resultado da última operação é negativo.
+    NEGATIVE = 9,
+}
+
+impl FlagIndex {
+    /// Retorna a posição do *bit* no *flag register*.
+    pub fn bit(&self) -> usize {
+        *self as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_index() {
+        for (i, register) in Register::GENERAL.iter().enumerate() {
+            assert_eq!(register.index(), Some(i));
+            assert_eq!(Register::from_index(i), Some(*register));
+        }
+
+        assert_eq!(Register::SP.index(), None);
+        assert!(!Register::FR.is_general_purpose());
+    }
+
+    #[test]
+    fn test_parse_register() {
+        assert_eq!("sp".parse(), Ok(Register::SP));
+        assert_eq!("R7".parse(), Ok(Register::R7));
+        assert_eq!(
+            "R8".parse::<Register>(),
+            Err(ParseRegisterError {
+                name: "R8".to_string()
+            })
+        );
+        assert!("R07".parse::<Register>().is_err());
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{DecodedInstruction, Instruction, Mov, Operands, StackOperand, Word};
+
+/// Base numérica usada para escrever endereços e valores imediatos.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Radix {
+    /// `0b10100`
+    Binary,
+    /// `20`
+    Decimal,
+    /// `0x0014`
+    #[default]
+    Hexadecimal,
+}
+
+/// Caixa usada para escrever os mnemônicos.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum MnemonicCase {
+    #[default]
+    Upper,
+    Lower,
+}
+
+/// Estilo de escrita de uma instrução em *assembly*.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct AsmStyle {
+    pub radix: Radix,
+    pub case: MnemonicCase,
+}
+
+impl AsmStyle {
+    /// Cria o estilo padrão: números em hexadecimal e mnemônicos em maiúsculas.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define a base numérica do estilo.
+    pub fn with_radix(mut self, radix: Radix) -> Self {
+        self.radix = radix;
+        self
+    }
+
+    /// Define a caixa dos mnemônicos do estilo.
+    pub fn with_case(mut self, case: MnemonicCase) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Escreve o número `v` na base do estilo.
+    pub fn number(&self, v: Word) -> String {
+        match self.radix {
+            Radix::Binary => format!("{v:#b}"),
+            Radix::Decimal => format!("{v}"),
+            Radix::Hexadecimal => format!("{v:#06x}"),
+        }
+    }
+}
+
+/// Uma [`DecodedInstruction`] escrita em *assembly* com um [`AsmStyle`]. Retornada por
+/// [`DecodedInstruction::display`].
+#[derive(Debug, Copy, Clone)]
+pub struct AsmDisplay<'a> {
+    instruction: &'a DecodedInstruction,
+    style: AsmStyle,
+    address_label: Option<&'a str>,
+}
+
+impl<'a> AsmDisplay<'a> {
+    /// Escreve o endereço `END` como o rótulo `label`, em vez de um número.
+    pub fn with_address_label(mut self, label: &'a str) -> Self {
+        self.address_label = Some(label);
+        self
+    }
+}
+
+impl DecodedInstruction {
+    /// Escreve a instrução em *assembly* com o estilo `style`. Endereços e valores imediatos
+    /// desconhecidos são escritos como `END` e `#NR`.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// let loadn = Instruction::decode_from(&[Word(0b111000_011_000_000_0), Word(0xff00)]).unwrap();
+    ///
+    /// assert_eq!("LOADN R3, #0xff00", loadn.to_string());
+    ///
+    /// let style = AsmStyle::new()
+    ///     .with_radix(Radix::Decimal)
+    ///     .with_case(MnemonicCase::Lower);
+    /// assert_eq!("loadn R3, #65280", loadn.display(style).to_string());
+    /// ```
+    pub fn display(&self, style: AsmStyle) -> AsmDisplay<'_> {
+        AsmDisplay {
+            instruction: self,
+            style,
+            address_label: None,
+        }
+    }
+}
+
+impl std::fmt::Display for AsmDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let DecodedInstruction {
+            instruction,
+            operands,
+            address,
+            immediate,
+        } = self.instruction;
+
+        let mnemonic = match self.style.case {
+            MnemonicCase::Upper => instruction.mnemonic().to_string(),
+            MnemonicCase::Lower => instruction.mnemonic().to_ascii_lowercase(),
+        };
+
+        let address = match (self.address_label, address) {
+            (Some(label), _) => label.to_string(),
+            (None, Some(address)) => self.style.number(*address),
+            (None, None) => "END".to_string(),
+        };
+
+        let immediate = match immediate {
+            Some(immediate) => format!("#{}", self.style.number(*immediate)),
+            None => "#NR".to_string(),
+        };
+
+        let format = instruction.format();
+        let mut operands: Vec<String> = match *operands {
+            Operands::None => vec![],
+            Operands::Rx { rx } => vec![rx.to_string()],
+            Operands::RxRy { rx, ry } => vec![rx.to_string(), ry.to_string()],
+            Operands::RxRyRz { rx, ry, rz } => {
+                vec![rx.to_string(), ry.to_string(), rz.to_string()]
+            }
+            Operands::Shift { rx, n } => vec![rx.to_string(), n.to_string()],
+            Operands::Mov(Mov::Register { rx, ry }) => vec![rx.to_string(), ry.to_string()],
+            Operands::Mov(Mov::FromSp { rx }) => vec![rx.to_string(), "SP".to_string()],
+            Operands::Mov(Mov::ToSp { rx }) => vec!["SP".to_string(), rx.to_string()],
+            Operands::Stack(StackOperand::Register(rx)) => vec![rx.to_string()],
+            Operands::Stack(StackOperand::FlagRegister) => vec!["FR".to_string()],
+        };
+
+        // `STORE END, Rx` e `STOREN END, #NR` escrevem o endereço antes dos demais operandos.
+        if format.has_address() {
+            let position = match instruction {
+                Instruction::STORE | Instruction::STOREN => 0,
+                _ => operands.len(),
+            };
+            operands.insert(position, address);
+        }
+
+        if format.has_immediate() {
+            operands.push(immediate);
+        }
+
+        if operands.is_empty() {
+            write!(f, "{mnemonic}")
+        } else {
+            write!(f, "{mnemonic} {}", operands.join(", "))
+        }
+    }
+}
+
+impl std::fmt::Display for DecodedInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.display(AsmStyle::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Register;
+
+    fn decode(words: &[u16]) -> DecodedInstruction {
+        let words: Vec<Word> = words.iter().copied().map(Word).collect();
+        Instruction::decode_from(&words).unwrap()
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            decode(&[0b100000_011_000_111_0]).to_string(),
+            "ADD R3, R0, R7"
+        );
+        assert_eq!(decode(&[0b000101_000_100_000_0]).to_string(), "PUSH FR");
+        assert_eq!(decode(&[0b110011_000_000_001_1]).to_string(), "MOV SP, R0");
+        assert_eq!(decode(&[0b110011_011_000_000_1]).to_string(), "MOV R3, SP");
+        assert_eq!(
+            decode(&[0b010000_111_000_100_1]).to_string(),
+            "SHIFTL0 R7, 9"
+        );
+        assert_eq!(decode(&[0b000000_000_000_000_0]).to_string(), "NOP");
+        assert_eq!(
+            decode(&[0b110001_011_000_000_0, 0x00ff]).to_string(),
+            "STORE 0x00ff, R3"
+        );
+        assert_eq!(
+            decode(&[0b111001_000_000_000_0, 0x00ff, 0b10100]).to_string(),
+            "STOREN 0x00ff, #0x0014"
+        );
+        assert_eq!(
+            decode(&[0b000010_000_000_000_0, 0x00ff]).to_string(),
+            "JMP 0x00ff"
+        );
+    }
+
+    #[test]
+    fn test_style() {
+        let storen = decode(&[0b111001_000_000_000_0, 0x00ff, 0b10100]);
+        let style = AsmStyle::new()
+            .with_radix(Radix::Binary)
+            .with_case(MnemonicCase::Lower);
+        assert_eq!(
+            storen.display(style).to_string(),
+            "storen 0b11111111, #0b10100"
+        );
+
+        let load = DecodedInstruction::new(Instruction::LOAD, Operands::Rx { rx: Register::R1 });
+        assert_eq!(load.to_string(), "LOAD R1, END");
+    }
+}
@@ -0,0 +1,210 @@
+use std::ops::RangeBounds;
+
+use thiserror::Error;
+
+use crate::locale::{key, message};
+use crate::{Bits, BitsError, InvalidInstruction, Locale, Localize, Register};
+
+/// Uma palavra de 16 *bits* da memória do Processador ICMC.
+///
+/// As instruções seguem o formato `opcode | Rx | Ry | Rz | c`:
+///
+/// ```txt
+/// 15    10 9  7 6  4 3  1 0
+/// ╭──────┬────┬────┬────┬─╮
+/// │opcode│ Rx │ Ry │ Rz │c│
+/// ╰──────┴────┴────┴────┴─╯
+/// ```
+///
+/// As palavras retornadas pela API podem ser convertidas sem perdas com `usize::from`. Já as
+/// funções que recebiam um `usize` possuem versões de compatibilidade, terminadas em `_usize`,
+/// que rejeitam valores maiores que uma palavra com [`WordOutOfRange`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Word(pub u16);
+
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub struct WordOutOfRange {
+    pub value: usize,
+}
+
+impl Localize for WordOutOfRange {
+    fn localize(&self, locale: Locale) -> String {
+        message(locale, key::WORD_OUT_OF_RANGE, &[("value", &self.value)])
+    }
+}
+
+/// Erro das versões de compatibilidade da API que recebem `usize`, como
+/// [`Instruction::decode_usize`](crate::Instruction::decode_usize).
+#[derive(Error, Debug, PartialEq)]
+#[error("{}", self.localize(Locale::Portuguese))]
+pub enum UsizeDecodeError {
+    WordOutOfRange(#[from] WordOutOfRange),
+    InvalidInstruction(#[from] InvalidInstruction),
+}
+
+impl Localize for UsizeDecodeError {
+    fn localize(&self, locale: Locale) -> String {
+        match self {
+            UsizeDecodeError::WordOutOfRange(e) => e.localize(locale),
+            UsizeDecodeError::InvalidInstruction(e) => e.localize(locale),
+        }
+    }
+}
+
+impl Word {
+    pub const ZERO: Word = Word(0);
+    pub const MAX: Word = Word(u16::MAX);
+
+    /// Retorna os *bits* 10 a 15, que identificam o OPCODE da instrução.
+    pub fn opcode(&self) -> u16 {
+        self.0.bits(10..=15)
+    }
+
+    /// Retorna o registrador codificado nos *bits* 7 a 9.
+    pub fn rx(&self) -> Register {
+        Register::GENERAL[usize::from(self.0.bits(7..=9))]
+    }
+
+    /// Retorna o registrador codificado nos *bits* 4 a 6.
+    pub fn ry(&self) -> Register {
+        Register::GENERAL[usize::from(self.0.bits(4..=6))]
+    }
+
+    /// Retorna o registrador codificado nos *bits* 1 a 3.
+    pub fn rz(&self) -> Register {
+        Register::GENERAL[usize::from(self.0.bits(1..=3))]
+    }
+
+    /// Retorna o *bit* 0, usado pelas instruções que consideram o *carry*.
+    pub fn c(&self) -> bool {
+        self.0.bits(0..=0) == 1
+    }
+
+    /// Cria uma palavra a partir de um valor com sinal, em complemento de dois.
+    ///
+    /// ## Exemplo
+    ///
+    /// ```
+    /// use isa::*;
+    ///
+    /// assert_eq!(Word(0xffff), Word::from_signed(-1));
+    /// assert_eq!(-1, Word(0xffff).to_signed());
+    /// ```
+    pub fn from_signed(v: i16) -> Word {
+        Word(v as u16)
+    }
+
+    /// Interpreta a palavra como um valor com sinal, em complemento de dois.
+    pub fn to_signed(&self) -> i16 {
+        self.0 as i16
+    }
+
+    /// Indica se o *bit* mais significativo está setado.
+    pub fn is_negative(&self) -> bool {
+        self.to_signed() < 0
+    }
+
+    /// Retorna o complemento de dois da palavra.
+    pub fn wrapping_neg(&self) -> Word {
+        Word(self.0.wrapping_neg())
+    }
+}
+
+impl Bits for Word {
+    fn bits<R: RangeBounds<usize>>(self, r: R) -> Self {
+        Word(self.0.bits(r))
+    }
+
+    fn checked_bits<R: RangeBounds<usize>>(self, r: R) -> Result<Self, BitsError> {
+        self.0.checked_bits(r).map(Word)
+    }
+
+    fn set_bits<R: RangeBounds<usize>>(self, b: Self, r: R) -> Self {
+        Word(self.0.set_bits(b.0, r))
+    }
+
+    fn checked_set_bits<R: RangeBounds<usize>>(self, b: Self, r: R) -> Result<Self, BitsError> {
+        self.0.checked_set_bits(b.0, r).map(Word)
+    }
+}
+
+impl From<u16> for Word {
+    fn from(value: u16) -> Self {
+        Word(value)
+    }
+}
+
+impl From<Word> for u16 {
+    fn from(value: Word) -> Self {
+        value.0
+    }
+}
+
+impl From<Word> for usize {
+    fn from(value: Word) -> Self {
+        usize::from(value.0)
+    }
+}
+
+impl TryFrom<usize> for Word {
+    type Error = WordOutOfRange;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        u16::try_from(value)
+            .map(Word)
+            .map_err(|_| WordOutOfRange { value })
+    }
+}
+
+impl std::fmt::Display for Word {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::fmt::Binary for Word {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        std::fmt::Binary::fmt(&self.0, f)
+    }
+}
+
+impl std::fmt::LowerHex for Word {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        std::fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl std::fmt::UpperHex for Word {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        std::fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields() {
+        let add = Word(0b100000_011_000_111_1); // ADDC R3, R0, R7
+        assert_eq!(add.opcode(), 0b100000);
+        assert_eq!(add.rx(), Register::R3);
+        assert_eq!(add.ry(), Register::R0);
+        assert_eq!(add.rz(), Register::R7);
+        assert!(add.c());
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Word::try_from(0xffff_usize), Ok(Word::MAX));
+        assert_eq!(
+            Word::try_from(0x10000_usize),
+            Err(WordOutOfRange { value: 0x10000 })
+        );
+        assert_eq!(usize::from(Word(42)), 42);
+        assert_eq!(Word(5).wrapping_neg().to_signed(), -5);
+        assert!(Word(0x8000).is_negative());
+        assert_eq!(format!("{:#06x}", Word(0xff)), "0x00ff");
+    }
+}